@@ -0,0 +1,178 @@
+use _2015::ErrorKind;
+
+use crate::TraversalError;
+
+/// Bytes counted at once, few enough for the counts to fit the `u8` lanes the
+/// compiler vectorizes the sums into.
+const CHUNK: usize = 64;
+
+/// Counts floors over raw bytes chunk by chunk, a fast path for huge inputs.
+///
+/// A chunk starting at least `CHUNK` floors above the basement cannot reach it, so only
+/// its number of `(` and `)` matters. The others are walked byte by byte to find the
+/// step the basement is first reached at.
+#[derive(PartialEq, Debug, Default)]
+pub struct FloorCounter {
+    floor: i64,
+    position: u64,
+    basement: Option<u64>,
+}
+
+impl FloorCounter {
+    pub fn new() -> FloorCounter {
+        FloorCounter::default()
+    }
+
+    pub fn floor(&self) -> i64 {
+        self.floor
+    }
+
+    /// The number of directions fed so far.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// The step the basement was first reached at, if it was.
+    pub fn first_basement(&self) -> Option<u64> {
+        self.basement
+    }
+
+    /// Counts the next `bytes` of the input, which may be fed in pieces of any size.
+    pub fn feed(&mut self, bytes: &[u8]) -> Result<(), TraversalError<ErrorKind>> {
+        self.count(bytes, false)
+    }
+
+    fn count(
+        &mut self,
+        bytes: &[u8],
+        stop_at_basement: bool,
+    ) -> Result<(), TraversalError<ErrorKind>> {
+        for chunk in bytes.chunks(CHUNK) {
+            if self.basement.is_none() && self.floor < chunk.len() as i64 {
+                self.walk(chunk, stop_at_basement)?;
+                if stop_at_basement && self.basement.is_some() {
+                    return Ok(());
+                }
+                continue;
+            }
+            let ups: u8 = chunk.iter().map(|&b| (b == b'(') as u8).sum();
+            let downs: u8 = chunk.iter().map(|&b| (b == b')') as u8).sum();
+            if (ups + downs) as usize != chunk.len() {
+                // walk again to locate the invalid byte
+                return self.walk(chunk, false);
+            }
+            self.floor += ups as i64 - downs as i64;
+            self.position += chunk.len() as u64;
+        }
+        Ok(())
+    }
+
+    fn walk(
+        &mut self,
+        chunk: &[u8],
+        stop_at_basement: bool,
+    ) -> Result<(), TraversalError<ErrorKind>> {
+        for &byte in chunk {
+            match byte {
+                b'(' => self.floor += 1,
+                b')' => self.floor -= 1,
+                _ => {
+                    return Err(TraversalError {
+                        index: self.position as usize,
+                        error: ErrorKind::UnexpectedChar(byte as char),
+                    });
+                }
+            }
+            self.position += 1;
+            if self.floor == -1 && self.basement.is_none() {
+                self.basement = Some(self.position);
+                if stop_at_basement {
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The floor reached after following every direction of `input`.
+pub fn count_floor(input: &[u8]) -> Result<i64, TraversalError<ErrorKind>> {
+    let mut counter = FloorCounter::new();
+    counter.feed(input)?;
+    Ok(counter.floor())
+}
+
+/// The step the basement is first reached at, directions after it are not read.
+pub fn find_basement(input: &[u8]) -> Result<Option<u64>, TraversalError<ErrorKind>> {
+    let mut counter = FloorCounter::new();
+    counter.count(input, true)?;
+    Ok(counter.first_basement())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        CharPolicy, FloorTracker, parse_directions_from_bytes, try_stop_at_basement,
+        try_traverse_building,
+    };
+    use _2015::random::Xorshift;
+
+    /// Directions drifting upwards when `up_bias` is above 50, with a rare invalid byte.
+    fn directions(random: &mut Xorshift, len: usize, up_bias: u64) -> Vec<u8> {
+        (0..len)
+            .map(|_| match random.below(1000) {
+                0 => b'x',
+                n if n % 100 < up_bias => b'(',
+                _ => b')',
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_agrees_with_iterator_implementation() {
+        let mut random = Xorshift::new(0x2015_0101);
+        for case in 0..300 {
+            let len = random.below(2000) as usize;
+            let input = directions(&mut random, len, 50 + case % 5);
+
+            let mut tracker = FloorTracker::new();
+            let expected = try_traverse_building(
+                &mut tracker,
+                parse_directions_from_bytes(&input),
+                CharPolicy::Strict,
+            )
+            .map(|_| tracker.current_floor() as i64);
+            assert_eq!(count_floor(&input), expected, "{case}");
+
+            let mut tracker = FloorTracker::new();
+            let expected = try_stop_at_basement(
+                &mut tracker,
+                parse_directions_from_bytes(&input),
+                CharPolicy::Strict,
+            )
+            .map(|steps| steps.map(u64::from));
+            assert_eq!(find_basement(&input), expected, "{case}");
+
+            // feeding in arbitrary pieces changes nothing
+            let mut counter = FloorCounter::new();
+            let mut rest = &input[..];
+            let mut fed = Ok(());
+            while !rest.is_empty() && fed.is_ok() {
+                let (piece, tail) = rest.split_at(random.below(100) as usize % rest.len() + 1);
+                fed = counter.feed(piece);
+                rest = tail;
+            }
+            assert_eq!(fed.map(|_| counter.floor()), count_floor(&input), "{case}");
+        }
+    }
+
+    #[test]
+    fn test_basement_found_past_skipped_chunks() {
+        let mut input = vec![b'('; 1000];
+        input.extend(vec![b')'; 1001]);
+        input.push(b'x');
+        assert_eq!(find_basement(&input), Ok(Some(2001)));
+        assert_eq!(count_floor(&input).unwrap_err().index, 2001);
+    }
+}
@@ -0,0 +1,92 @@
+use std::collections::BTreeMap;
+
+/// The floors Santa went through, recorded by `FloorTracker::recording`.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct FloorHistory {
+    /// floor after each step, Santa starts on floor 0 before the first one
+    floors: Vec<i32>,
+}
+
+impl FloorHistory {
+    pub(crate) fn record(&mut self, floor: i32) {
+        self.floors.push(floor);
+    }
+
+    /// The floor after each step.
+    pub fn trajectory(&self) -> &[i32] {
+        &self.floors
+    }
+
+    /// Every floor Santa stood on, the starting floor included.
+    fn visited(&self) -> impl Iterator<Item = i32> + '_ {
+        std::iter::once(0).chain(self.floors.iter().copied())
+    }
+
+    pub fn highest(&self) -> i32 {
+        self.visited().max().unwrap_or_default()
+    }
+
+    pub fn lowest(&self) -> i32 {
+        self.visited().min().unwrap_or_default()
+    }
+
+    /// The number of steps taken when `floor` is first reached, `0` for the starting floor.
+    pub fn first_reached(&self, floor: i32) -> Option<u32> {
+        self.visited()
+            .position(|f| f == floor)
+            .map(|step| step as u32)
+    }
+
+    /// How many steps ended on each floor.
+    pub fn steps_per_floor(&self) -> BTreeMap<i32, u32> {
+        let mut steps = BTreeMap::new();
+        for floor in &self.floors {
+            *steps.entry(*floor).or_default() += 1;
+        }
+        steps
+    }
+
+    /// How many times Santa went down into the basement.
+    pub fn basement_visits(&self) -> u32 {
+        self.visited()
+            .zip(self.floors.iter())
+            .filter(|(from, to)| *from >= 0 && **to < 0)
+            .count() as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(floors: &[i32]) -> FloorHistory {
+        FloorHistory {
+            floors: floors.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_statistics() {
+        // "(())))(()"
+        let history = history(&[1, 2, 1, 0, -1, -2, -1, 0, -1]);
+        assert_eq!((history.highest(), history.lowest()), (2, -2));
+        assert_eq!(history.first_reached(0), Some(0));
+        assert_eq!(history.first_reached(-1), Some(5));
+        assert_eq!(history.first_reached(-2), Some(6));
+        assert_eq!(history.first_reached(3), None);
+        assert_eq!(
+            history.steps_per_floor(),
+            BTreeMap::from([(-2, 1), (-1, 3), (0, 2), (1, 2), (2, 1)])
+        );
+        assert_eq!(history.basement_visits(), 2);
+    }
+
+    #[test]
+    fn test_empty_history() {
+        let history = FloorHistory::default();
+        assert_eq!((history.highest(), history.lowest()), (0, 0));
+        assert_eq!(history.first_reached(0), Some(0));
+        assert!(history.steps_per_floor().is_empty());
+        assert_eq!(history.basement_visits(), 0);
+    }
+}
@@ -1,19 +1,47 @@
-use std::{convert::TryFrom, str::Chars};
+pub mod fast;
+mod history;
+pub mod parallel;
 
-#[derive(PartialEq, Debug)]
+pub use history::FloorHistory;
+
+use _2015::{ErrorKind, parse};
+use aoc_core::{Answer, Error, Solver};
+use std::{
+    convert::TryFrom,
+    fmt::{Debug, Display},
+    io::{BufReader, Read},
+    iter::Copied,
+    slice,
+    str::Chars,
+};
+
+#[derive(PartialEq, Debug, Clone, Copy)]
 pub enum Direction {
     Up,
     Down,
 }
 
 impl TryFrom<char> for Direction {
-    type Error = String;
+    type Error = ErrorKind;
 
     fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
             '(' => Ok(Direction::Up),
             ')' => Ok(Direction::Down),
-            _ => Err(format!("Invalid direction character: {}", value)),
+            _ => Err(ErrorKind::UnexpectedChar(value)),
+        }
+    }
+}
+
+/// A byte outside of ASCII is reported as the `char` of the same value.
+impl TryFrom<u8> for Direction {
+    type Error = ErrorKind;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            b'(' => Ok(Direction::Up),
+            b')' => Ok(Direction::Down),
+            _ => Err(ErrorKind::UnexpectedChar(value as char)),
         }
     }
 }
@@ -21,6 +49,13 @@ impl TryFrom<char> for Direction {
 pub struct FloorTracker {
     floor: i32,
     steps_taken: u32,
+    history: Option<FloorHistory>,
+}
+
+impl Default for FloorTracker {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl FloorTracker {
@@ -28,6 +63,14 @@ impl FloorTracker {
         FloorTracker {
             floor: 0,
             steps_taken: 0,
+            history: None,
+        }
+    }
+    /// A tracker that also records every floor it goes through.
+    pub fn recording() -> FloorTracker {
+        FloorTracker {
+            history: Some(FloorHistory::default()),
+            ..FloorTracker::new()
         }
     }
     fn next(&mut self, direction: Direction) {
@@ -35,7 +78,10 @@ impl FloorTracker {
             Direction::Up => self.floor += 1,
             Direction::Down => self.floor -= 1,
         }
-        self.steps_taken += 1
+        self.steps_taken += 1;
+        if let Some(history) = &mut self.history {
+            history.record(self.floor);
+        }
     }
     pub fn current_floor(&self) -> i32 {
         self.floor
@@ -43,6 +89,10 @@ impl FloorTracker {
     pub fn steps_taken(&self) -> u32 {
         self.steps_taken
     }
+    /// The floors gone through, if the tracker is recording.
+    pub fn history(&self) -> Option<&FloorHistory> {
+        self.history.as_ref()
+    }
 }
 
 pub struct DirectionIter<I: Iterator<Item = char>> {
@@ -50,19 +100,35 @@ pub struct DirectionIter<I: Iterator<Item = char>> {
 }
 
 impl<I: Iterator<Item = char>> DirectionIter<I> {
-    fn new(iter: I) -> Self {
+    pub fn new(iter: I) -> Self {
         Self { chars: iter }
     }
 }
 
-impl Iterator for DirectionIter<Chars<'_>> {
-    type Item = Result<Direction, String>;
+impl<I: Iterator<Item = char>> Iterator for DirectionIter<I> {
+    type Item = Result<Direction, ErrorKind>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.chars.next() {
-            Some(c) => Some(Direction::try_from(c)),
-            None => None,
-        }
+        self.chars.next().map(Direction::try_from)
+    }
+}
+
+/// Like `DirectionIter`, over raw bytes.
+pub struct DirectionBytes<I: Iterator<Item = u8>> {
+    bytes: I,
+}
+
+impl<I: Iterator<Item = u8>> DirectionBytes<I> {
+    pub fn new(iter: I) -> Self {
+        Self { bytes: iter }
+    }
+}
+
+impl<I: Iterator<Item = u8>> Iterator for DirectionBytes<I> {
+    type Item = Result<Direction, ErrorKind>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.bytes.next().map(Direction::try_from)
     }
 }
 
@@ -70,21 +136,145 @@ pub fn parse_directions_from_str(input: &str) -> DirectionIter<Chars<'_>> {
     DirectionIter::new(input.chars())
 }
 
-pub fn traverse_building(
+pub fn parse_directions_from_bytes(input: &[u8]) -> DirectionBytes<Copied<slice::Iter<'_, u8>>> {
+    DirectionBytes::new(input.iter().copied())
+}
+
+/// Reads directions from `reader` as they are needed, so inputs of any size are
+/// traversed without being loaded in memory first.
+pub fn parse_directions_from_reader<R: Read>(
+    reader: R,
+) -> impl Iterator<Item = Result<Direction, Error>> {
+    BufReader::new(reader)
+        .bytes()
+        .map(|byte| Ok(Direction::try_from(byte?)?))
+}
+
+/// What to do with characters that are not directions.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum CharPolicy {
+    #[default]
+    Strict,
+    /// skips whitespace, such as the newline ending an input file
+    SkipWhitespace,
+    /// skips every character other than `(` and `)`
+    SkipUnknown,
+}
+
+impl CharPolicy {
+    pub fn skips(self, c: char) -> bool {
+        match self {
+            CharPolicy::Strict => false,
+            CharPolicy::SkipWhitespace => c.is_whitespace(),
+            CharPolicy::SkipUnknown => true,
+        }
+    }
+}
+
+/// Errors that may stand for a character which is not a direction, so a `CharPolicy`
+/// can tell whether to skip them.
+pub trait UnexpectedChar {
+    fn unexpected_char(&self) -> Option<char>;
+}
+
+impl UnexpectedChar for ErrorKind {
+    fn unexpected_char(&self) -> Option<char> {
+        match self {
+            ErrorKind::UnexpectedChar(c) => Some(*c),
+            _ => None,
+        }
+    }
+}
+
+impl UnexpectedChar for Error {
+    fn unexpected_char(&self) -> Option<char> {
+        self.downcast_ref::<ErrorKind>()
+            .and_then(ErrorKind::unexpected_char)
+    }
+}
+
+/// The first error met while traversing, `index` being the position of the offending
+/// character (or byte) in the directions.
+#[derive(PartialEq, Debug)]
+pub struct TraversalError<E> {
+    pub index: usize,
+    pub error: E,
+}
+
+impl<E: Display> Display for TraversalError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "direction {}: {}", self.index + 1, self.error)
+    }
+}
+
+impl<E: Debug + Display> std::error::Error for TraversalError<E> {}
+
+/// Follows `directions` until `stop` holds, skipping the characters `policy` allows.
+/// Returns whether it stopped before running out of directions.
+fn try_walk<E: UnexpectedChar>(
+    floor_tracker: &mut FloorTracker,
+    directions: impl Iterator<Item = Result<Direction, E>>,
+    policy: CharPolicy,
+    stop: impl Fn(&FloorTracker) -> bool,
+) -> Result<bool, TraversalError<E>> {
+    for (index, direction) in directions.enumerate() {
+        match direction {
+            Ok(direction) => floor_tracker.next(direction),
+            Err(e) if e.unexpected_char().is_some_and(|c| policy.skips(c)) => continue,
+            Err(error) => return Err(TraversalError { index, error }),
+        }
+        if stop(floor_tracker) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Like `traverse_building`, returning the first invalid direction instead of panicking.
+pub fn try_traverse_building<E: UnexpectedChar>(
+    floor_tracker: &mut FloorTracker,
+    directions: impl Iterator<Item = Result<Direction, E>>,
+    policy: CharPolicy,
+) -> Result<(), TraversalError<E>> {
+    try_walk(floor_tracker, directions, policy, |_| false)?;
+    Ok(())
+}
+
+/// Like `stop_at_basement`, returning the first invalid direction instead of panicking.
+pub fn try_stop_at_basement<E: UnexpectedChar>(
+    floor_tracker: &mut FloorTracker,
+    directions: impl Iterator<Item = Result<Direction, E>>,
+    policy: CharPolicy,
+) -> Result<Option<u32>, TraversalError<E>> {
+    let reached = try_walk(floor_tracker, directions, policy, |tracker| {
+        tracker.floor == -1
+    })?;
+    Ok(reached.then_some(floor_tracker.steps_taken))
+}
+
+/// # Panics
+///
+/// On the first invalid direction, see `try_traverse_building`.
+pub fn traverse_building<E: Debug>(
     floor_tracker: &mut FloorTracker,
-    directions: impl Iterator<Item = Result<Direction, String>>,
+    directions: impl Iterator<Item = Result<Direction, E>>,
 ) {
     for direction in directions {
-        floor_tracker.next(direction.expect("direction should be either '(' for up or ')' for down"));
+        floor_tracker
+            .next(direction.expect("direction should be either '(' for up or ')' for down"));
     }
 }
 
-pub fn stop_at_basement(
+/// # Panics
+///
+/// On the first invalid direction, see `try_stop_at_basement`.
+pub fn stop_at_basement<E: Debug>(
     floor_tracker: &mut FloorTracker,
-    directions: impl Iterator<Item = Result<Direction, String>>,
+    directions: impl Iterator<Item = Result<Direction, E>>,
 ) -> Option<u32> {
     for direction in directions {
-        floor_tracker.next(direction.expect("direction should be either '(' for up or ')' for down"));
+        floor_tracker
+            .next(direction.expect("direction should be either '(' for up or ')' for down"));
         if floor_tracker.floor == -1 {
             return Some(floor_tracker.steps_taken);
         }
@@ -92,10 +282,57 @@ pub fn stop_at_basement(
     None
 }
 
+pub struct Day01;
+
+impl Day01 {
+    /// Inputs may end with a newline.
+    pub const POLICY: CharPolicy = CharPolicy::SkipWhitespace;
+}
+
+impl Solver for Day01 {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 1;
+
+    type Input<'a> = Vec<Direction>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        let directions = parse::chars(input, |c| match Direction::try_from(c) {
+            Ok(direction) => Ok(Some(direction)),
+            Err(_) if Day01::POLICY.skips(c) => Ok(None),
+            Err(e) => Err(e),
+        })?;
+        Ok(directions.into_iter().flatten().collect())
+    }
+
+    fn part1(directions: &Self::Input<'_>) -> Answer {
+        let mut floor_tracker = FloorTracker::new();
+        traverse_building(
+            &mut floor_tracker,
+            directions.iter().copied().map(Ok::<_, ErrorKind>),
+        );
+        floor_tracker.current_floor().into()
+    }
+
+    fn part2(directions: &Self::Input<'_>) -> Answer {
+        let mut floor_tracker = FloorTracker::new();
+        stop_at_basement(
+            &mut floor_tracker,
+            directions.iter().copied().map(Ok::<_, ErrorKind>),
+        );
+        floor_tracker.steps_taken().into()
+    }
+}
+
+aoc_core::examples!(
+    Day01:
+    balanced, alternating, up_three, up_three_mixed, up_three_late, down_one, down_one_early,
+    down_three, down_three_mixed, basement_first, basement_fifth,
+);
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+
     #[test]
     fn test_floor_tracker_up_and_down() {
         let mut tracker = FloorTracker::new();
@@ -108,10 +345,10 @@ mod tests {
     #[should_panic]
     fn test_floor_tracker_invalid_direction() {
         let mut tracker = FloorTracker::new();
-        let dirs = parse_directions_from_str("[");        
+        let dirs = parse_directions_from_str("[");
         traverse_building(&mut tracker, dirs);
     }
-    
+
     #[test]
     fn test_stop_at_basement_reached() {
         let mut tracker = FloorTracker::new();
@@ -127,4 +364,92 @@ mod tests {
         let steps_to_basement = stop_at_basement(&mut tracker, directions);
         assert_eq!(steps_to_basement, None);
     }
+
+    #[test]
+    fn test_directions_from_any_source() {
+        let from_str: Vec<_> = parse_directions_from_str("(()").collect();
+        let from_chars: Vec<_> = DirectionIter::new(['(', '(', ')'].into_iter()).collect();
+        let from_bytes: Vec<_> = parse_directions_from_bytes(b"(()").collect();
+        assert_eq!(from_str, from_chars);
+        assert_eq!(from_str, from_bytes);
+        assert_eq!(
+            parse_directions_from_bytes(&[0xe9]).next(),
+            Some(Err(ErrorKind::UnexpectedChar('\u{e9}')))
+        );
+    }
+
+    #[test]
+    fn test_traverse_from_reader() {
+        let mut tracker = FloorTracker::new();
+        let up = std::io::repeat(b'(').take(1_000_000);
+        let down = std::io::repeat(b')').take(1_000_001);
+        let directions = parse_directions_from_reader(up.chain(down));
+        assert_eq!(stop_at_basement(&mut tracker, directions), Some(2_000_001));
+        let mut directions = parse_directions_from_reader(&b"(x"[..]);
+        assert!(directions.next().unwrap().is_ok());
+        assert!(directions.next().unwrap().is_err());
+    }
+
+    #[test]
+    fn test_try_traverse_reports_index() {
+        let mut tracker = FloorTracker::new();
+        let directions = parse_directions_from_str("(()x)");
+        assert_eq!(
+            try_traverse_building(&mut tracker, directions, CharPolicy::Strict),
+            Err(TraversalError {
+                index: 3,
+                error: ErrorKind::UnexpectedChar('x')
+            })
+        );
+        let mut tracker = FloorTracker::new();
+        let directions = parse_directions_from_str("(()\n");
+        let error = try_traverse_building(&mut tracker, directions, CharPolicy::Strict);
+        assert_eq!(error.unwrap_err().index, 3);
+    }
+
+    #[test]
+    fn test_try_traverse_policies() {
+        let floor = |input, policy| {
+            let mut tracker = FloorTracker::new();
+            try_traverse_building(&mut tracker, parse_directions_from_str(input), policy)
+                .map(|_| tracker.current_floor())
+        };
+        assert_eq!(floor("(( (\n", CharPolicy::SkipWhitespace), Ok(3));
+        assert!(floor("((x(\n", CharPolicy::SkipWhitespace).is_err());
+        assert_eq!(floor("((x(\n", CharPolicy::SkipUnknown), Ok(3));
+
+        let mut tracker = FloorTracker::new();
+        let directions = parse_directions_from_reader(&b"( ))("[..]);
+        let basement = try_stop_at_basement(&mut tracker, directions, CharPolicy::SkipWhitespace);
+        assert_eq!(basement.unwrap(), Some(3));
+        let mut tracker = FloorTracker::new();
+        let directions = parse_directions_from_reader(&b"(x"[..]);
+        let error = try_stop_at_basement(&mut tracker, directions, CharPolicy::SkipWhitespace);
+        assert_eq!(error.unwrap_err().index, 1);
+    }
+
+    #[test]
+    fn test_recording_tracker() {
+        let input = "(()))())((";
+        let mut tracker = FloorTracker::recording();
+        traverse_building(&mut tracker, parse_directions_from_str(input));
+        let history = tracker.history().unwrap();
+        assert_eq!(history.trajectory().len(), input.len());
+        assert_eq!(history.trajectory().last(), Some(&tracker.current_floor()));
+        let mut stopping = FloorTracker::new();
+        assert_eq!(
+            history.first_reached(-1),
+            stop_at_basement(&mut stopping, parse_directions_from_str(input))
+        );
+        assert!(FloorTracker::new().history().is_none());
+    }
+
+    #[test]
+    fn test_solver() {
+        let directions = Day01::parse("()())").unwrap();
+        assert_eq!(Day01::part1(&directions), Answer::from(-1));
+        assert_eq!(Day01::part2(&directions), Answer::from(5u32));
+        assert!(Day01::parse("(x)").is_err());
+        assert_eq!(Day01::parse("()\n").unwrap().len(), 2);
+    }
 }
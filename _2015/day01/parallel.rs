@@ -0,0 +1,200 @@
+use _2015::ErrorKind;
+use rayon::prelude::*;
+
+use crate::TraversalError;
+
+/// Bytes summarized by one task.
+pub const CHUNK: usize = 1 << 20;
+
+/// Bytes counted at once within a chunk, as in `fast`.
+const BLOCK: usize = 64;
+
+/// What a run of directions does to the floor, enough to combine runs in any grouping.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Summary {
+    /// floor change over the whole run
+    pub delta: i64,
+    /// lowest floor reached relative to the start, `0` at most
+    pub min_prefix: i64,
+}
+
+impl Summary {
+    pub const EMPTY: Summary = Summary {
+        delta: 0,
+        min_prefix: 0,
+    };
+
+    /// Summarizes `bytes`, or returns the index of the first invalid one.
+    ///
+    /// Like `FloorCounter`, blocks that cannot go below the lowest floor so far are only
+    /// counted, the others are walked byte by byte.
+    pub fn of(bytes: &[u8]) -> Result<Summary, usize> {
+        let mut summary = Summary::EMPTY;
+        for (block_index, block) in bytes.chunks(BLOCK).enumerate() {
+            let ups: u8 = block.iter().map(|&b| (b == b'(') as u8).sum();
+            let downs: u8 = block.iter().map(|&b| (b == b')') as u8).sum();
+            if (ups + downs) as usize != block.len() {
+                let i = block.iter().position(|b| !matches!(b, b'(' | b')'));
+                return Err(block_index * BLOCK + i.expect("the block has an invalid byte"));
+            }
+            if summary.delta - (block.len() as i64) < summary.min_prefix {
+                for &byte in block {
+                    summary.delta += if byte == b'(' { 1 } else { -1 };
+                    summary.min_prefix = summary.min_prefix.min(summary.delta);
+                }
+            } else {
+                summary.delta += ups as i64 - downs as i64;
+            }
+        }
+        Ok(summary)
+    }
+
+    /// The summary of this run followed by `next`. Associative, with `EMPTY` as identity.
+    pub fn then(self, next: Summary) -> Summary {
+        Summary {
+            delta: self.delta + next.delta,
+            min_prefix: self.min_prefix.min(self.delta + next.min_prefix),
+        }
+    }
+}
+
+fn invalid(input: &[u8], index: usize) -> TraversalError<ErrorKind> {
+    TraversalError {
+        index,
+        error: ErrorKind::UnexpectedChar(input[index] as char),
+    }
+}
+
+fn summarize(input: &[u8], chunk: usize) -> Result<Summary, TraversalError<ErrorKind>> {
+    input
+        .par_chunks(chunk)
+        .enumerate()
+        .map(|(i, bytes)| Summary::of(bytes).map_err(|j| invalid(input, i * chunk + j)))
+        // the reduction keeps the order of the chunks, so the first error wins
+        .reduce(|| Ok(Summary::EMPTY), |left, right| Ok(left?.then(right?)))
+}
+
+fn first_basement(input: &[u8], chunk: usize) -> Result<Option<u64>, TraversalError<ErrorKind>> {
+    let summaries: Vec<Result<Summary, usize>> = input.par_chunks(chunk).map(Summary::of).collect();
+    let mut floor = 0;
+    for (i, summary) in summaries.into_iter().enumerate() {
+        match summary {
+            Ok(summary) if floor + summary.min_prefix > -1 => floor += summary.delta,
+            // the basement is reached in this chunk, or an invalid byte stops the walk
+            _ => {
+                let start = i * chunk;
+                let end = input.len().min(start + chunk);
+                for (index, byte) in input[start..end].iter().enumerate() {
+                    match byte {
+                        b'(' => floor += 1,
+                        b')' => floor -= 1,
+                        _ => return Err(invalid(input, start + index)),
+                    }
+                    if floor == -1 {
+                        return Ok(Some((start + index + 1) as u64));
+                    }
+                }
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// The floor reached after following every direction of `input`, computed in parallel.
+pub fn count_floor(input: &[u8]) -> Result<i64, TraversalError<ErrorKind>> {
+    Ok(summarize(input, CHUNK)?.delta)
+}
+
+/// The step the basement is first reached at, chunks are summarized in parallel.
+pub fn find_basement(input: &[u8]) -> Result<Option<u64>, TraversalError<ErrorKind>> {
+    first_basement(input, CHUNK)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FloorTracker, parse_directions_from_bytes, stop_at_basement, traverse_building};
+    use _2015::random::Xorshift;
+
+    fn directions(seed: u64, len: usize) -> Vec<u8> {
+        let mut random = Xorshift::new(seed);
+        (0..len)
+            .map(|_| if random.below(2) == 0 { b'(' } else { b')' })
+            .collect()
+    }
+
+    #[test]
+    fn test_summaries_combine_associatively() {
+        let input = directions(7, 300);
+        let whole = Summary::of(&input).unwrap();
+        for split in [0, 1, 99, 150, 300] {
+            for second in [split, (split + 300) / 2, 300] {
+                let [a, b, c] = [&input[..split], &input[split..second], &input[second..]]
+                    .map(|bytes| Summary::of(bytes).unwrap());
+                assert_eq!(a.then(b).then(c), whole);
+                assert_eq!(a.then(b.then(c)), whole);
+                assert_eq!(Summary::EMPTY.then(a), a);
+                assert_eq!(a.then(Summary::EMPTY), a);
+            }
+        }
+    }
+
+    #[test]
+    fn test_agrees_with_stop_at_basement() {
+        for seed in 1..40 {
+            let input = directions(seed, 1000);
+            let mut tracker = FloorTracker::new();
+            traverse_building(&mut tracker, parse_directions_from_bytes(&input));
+            let mut stopping = FloorTracker::new();
+            let basement = stop_at_basement(&mut stopping, parse_directions_from_bytes(&input));
+            for chunk in [1, 3, 64, 333, 1000, 4096] {
+                assert_eq!(
+                    summarize(&input, chunk).map(|s| s.delta),
+                    Ok(tracker.current_floor() as i64)
+                );
+                assert_eq!(
+                    first_basement(&input, chunk),
+                    Ok(basement.map(u64::from)),
+                    "seed {seed}, chunk {chunk}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_summary_of_long_runs() {
+        let mut input = directions(11, 5000);
+        input.extend(vec![b'('; 300]);
+        input.extend(vec![b')'; 700]);
+        let mut floor = 0;
+        let mut min_prefix = 0;
+        for byte in &input {
+            floor += if *byte == b'(' { 1 } else { -1 };
+            min_prefix = min_prefix.min(floor);
+        }
+        let expected = Summary {
+            delta: floor,
+            min_prefix,
+        };
+        assert_eq!(Summary::of(&input), Ok(expected));
+        input[4321] = b'x';
+        assert_eq!(Summary::of(&input), Err(4321));
+    }
+
+    #[test]
+    fn test_reports_first_invalid_byte() {
+        let mut input = directions(3, 1000);
+        input[700] = b'x';
+        input[300] = b'y';
+        for chunk in [1, 64, 512, 1000] {
+            let error = summarize(&input, chunk).unwrap_err();
+            assert_eq!(error, invalid(&input, 300));
+        }
+        let mut input = vec![b')'; 10];
+        input[5] = b'x';
+        assert_eq!(first_basement(&input, 4), Ok(Some(1)));
+        assert_eq!(first_basement(&input[1..], 2), Ok(Some(1)));
+        let input = b"((x))))";
+        assert_eq!(first_basement(input, 2).unwrap_err().index, 2);
+    }
+}
@@ -1,15 +1,10 @@
-use _2015_day01::DirectionIter;
-use _2015_day01::FloorTracker;
-use _2015_day01::parse_directions_from_str;
-use _2015_day01::traverse_building;
+use _2015_day01::Day01;
+use aoc_core::{Solver, input};
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let input_file_content = std::fs::read_to_string("_2015/day01/input1.txt")?;
-    let directions: DirectionIter<_> = parse_directions_from_str(&input_file_content);
-    let mut floor_tracker = FloorTracker::new();
-    traverse_building(&mut floor_tracker, directions);
-    println!("{:?}", floor_tracker.current_floor());
+fn main() -> Result<(), aoc_core::Error> {
+    let input_file_content =
+        input::read(Day01::YEAR, Day01::DAY, std::env::args().nth(1).as_deref())?;
+    let directions = Day01::parse(&input_file_content)?;
+    println!("{}", Day01::part1(&directions));
     Ok(())
 }
-
-
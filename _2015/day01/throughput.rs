@@ -0,0 +1,88 @@
+//! Measures how fast floors are counted over a generated input of several gigabytes,
+//! fed in pieces so it never has to fit in memory:
+//!
+//! ```sh
+//! cargo run --release --bin _2015_day01_throughput -- 4
+//! ```
+
+use _2015::random::Xorshift;
+use _2015_day01::{
+    CharPolicy, FloorTracker, fast::FloorCounter, parallel, parse_directions_from_bytes,
+    try_traverse_building,
+};
+use std::time::{Duration, Instant};
+
+const PIECE: usize = 1 << 20;
+const GIB: u64 = 1 << 30;
+/// The iterator implementation is too slow to go through gigabytes.
+const ITERATOR_BYTES: u64 = 256 << 20;
+/// The parallel implementation needs the whole input in memory.
+const PARALLEL_BYTES: u64 = GIB;
+
+/// Pieces of directions drifting upwards, so the basement is never reached.
+fn pieces() -> Vec<Vec<u8>> {
+    let mut random = Xorshift::new(0x2015_0101);
+    (0..16)
+        .map(|_| {
+            (0..PIECE)
+                .map(|_| if random.below(100) < 55 { b'(' } else { b')' })
+                .collect()
+        })
+        .collect()
+}
+
+fn report(name: &str, bytes: u64, elapsed: Duration, floor: i64) {
+    let gib_per_s = bytes as f64 / GIB as f64 / elapsed.as_secs_f64();
+    println!(
+        "{name:<10} {bytes:>12} bytes in {elapsed:>12.3?}  {gib_per_s:>7.2} GiB/s  floor {floor}"
+    );
+}
+
+fn main() -> Result<(), aoc_core::Error> {
+    let gib: u64 = match std::env::args().nth(1) {
+        Some(gib) => gib.parse()?,
+        None => 4,
+    };
+    let pieces = pieces();
+    let total = gib * GIB;
+
+    let start = Instant::now();
+    let mut counter = FloorCounter::new();
+    for piece in pieces.iter().cycle().take((total / PIECE as u64) as usize) {
+        counter.feed(piece)?;
+    }
+    report("fast", counter.position(), start.elapsed(), counter.floor());
+
+    let input: Vec<u8> = pieces
+        .iter()
+        .cycle()
+        .take((PARALLEL_BYTES.min(total) / PIECE as u64) as usize)
+        .flatten()
+        .copied()
+        .collect();
+    let start = Instant::now();
+    let floor = parallel::count_floor(&input)?;
+    report("parallel", input.len() as u64, start.elapsed(), floor);
+    drop(input);
+
+    let start = Instant::now();
+    let mut tracker = FloorTracker::new();
+    for piece in pieces
+        .iter()
+        .cycle()
+        .take((ITERATOR_BYTES / PIECE as u64) as usize)
+    {
+        try_traverse_building(
+            &mut tracker,
+            parse_directions_from_bytes(piece),
+            CharPolicy::Strict,
+        )?;
+    }
+    report(
+        "iterator",
+        tracker.steps_taken() as u64,
+        start.elapsed(),
+        tracker.current_floor() as i64,
+    );
+    Ok(())
+}
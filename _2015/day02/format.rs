@@ -0,0 +1,359 @@
+use std::fmt::Display;
+
+use _2015::{ErrorKind, ParseError, parse};
+
+use crate::{Dimension, PresentBox};
+
+/// The separator of the puzzle input, the only one allowed by `DimensionFormat::STRICT`.
+const DELIMITER: char = 'x';
+
+/// Separators accepted between dimensions, whitespace separates them too.
+const SEPARATORS: [char; 4] = [DELIMITER, '*', '×', ','];
+
+/// The dimensions of a line, in order, as named in errors.
+const DIMENSIONS: [&str; 3] = ["length", "width", "height"];
+
+const MISSING: [&str; 3] = ["a length", "a width", "a height"];
+
+/// The unit of a dimension, given as a suffix such as `12in` or `12 in`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Unit {
+    /// the unit of the puzzle
+    #[default]
+    Feet,
+    Inches,
+    Centimeters,
+}
+
+impl Unit {
+    pub const ALL: [Unit; 3] = [Unit::Feet, Unit::Inches, Unit::Centimeters];
+
+    pub fn suffix(self) -> &'static str {
+        match self {
+            Unit::Feet => "ft",
+            Unit::Inches => "in",
+            Unit::Centimeters => "cm",
+        }
+    }
+
+    fn from_suffix(suffix: &str) -> Option<Unit> {
+        Unit::ALL.into_iter().find(|unit| unit.suffix() == suffix)
+    }
+
+    fn micrometers(self) -> u128 {
+        match self {
+            Unit::Feet => 304_800,
+            Unit::Inches => 25_400,
+            Unit::Centimeters => 10_000,
+        }
+    }
+
+    /// `value` in this unit converted to `target`, rounded up so the box still fits.
+    fn convert<T: Dimension>(self, value: T, target: Unit) -> Option<T> {
+        let micrometers = value.into().checked_mul(self.micrometers())?;
+        T::try_from(micrometers.div_ceil(target.micrometers())).ok()
+    }
+}
+
+impl Display for Unit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Unit::Feet => "feet",
+            Unit::Inches => "inches",
+            Unit::Centimeters => "centimeters",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// How the dimensions of a present are written.
+///
+/// `LENIENT` accepts `2x3x4` as well as `2 * 3 * 4`, `2×3×4`, `2, 3, 4` or `2 3 4`, each
+/// dimension with an optional unit, converted to `unit`. `STRICT` only accepts the
+/// canonical `2x3x4`, without whitespace, units, signs or leading zeros.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DimensionFormat {
+    pub strict: bool,
+    /// the unit of the parsed dimensions, and of dimensions given without one
+    pub unit: Unit,
+}
+
+impl Default for DimensionFormat {
+    fn default() -> Self {
+        DimensionFormat::LENIENT
+    }
+}
+
+/// A position in a line being parsed.
+struct Cursor<'a> {
+    line: &'a str,
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn rest(&self) -> &'a str {
+        &self.line[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn separator(&mut self) {
+        self.skip_whitespace();
+        if let Some(c) = self.rest().chars().next()
+            && SEPARATORS.contains(&c)
+        {
+            self.pos += c.len_utf8();
+            self.skip_whitespace();
+        }
+    }
+
+    /// The characters up to the next separator or whitespace, empty if there is none.
+    fn token(&mut self) -> &'a str {
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| c.is_whitespace() || SEPARATORS.contains(&c))
+            .unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    /// A unit following a dimension after whitespace, nothing is read if there is none.
+    fn unit(&mut self) -> Option<Unit> {
+        let start = self.pos;
+        self.skip_whitespace();
+        let unit = Unit::from_suffix(self.token());
+        if unit.is_none() {
+            self.pos = start;
+        }
+        unit
+    }
+}
+
+impl DimensionFormat {
+    pub const LENIENT: DimensionFormat = DimensionFormat {
+        strict: false,
+        unit: Unit::Feet,
+    };
+
+    pub const STRICT: DimensionFormat = DimensionFormat {
+        strict: true,
+        unit: Unit::Feet,
+    };
+
+    /// Parses the dimensions of a present, pointing at the first one missing or invalid,
+    /// or at whatever follows the third one.
+    pub fn parse<T: Dimension>(&self, line: &str) -> Result<PresentBox<T>, ParseError> {
+        if self.strict {
+            check_canonical(line)?;
+        }
+        let mut cursor = Cursor { line, pos: 0 };
+        let mut dimensions = Vec::with_capacity(3);
+        for i in 0..3 {
+            match i {
+                0 => cursor.skip_whitespace(),
+                _ => cursor.separator(),
+            }
+            let token = cursor.token();
+            if token.is_empty() {
+                // an empty token still points where the dimension is missing
+                let kind = ErrorKind::MissingToken {
+                    expected: MISSING[i],
+                };
+                return Err(ParseError::at_token(kind, line, token));
+            }
+            dimensions.push(self.dimension(line, token, &mut cursor, DIMENSIONS[i])?);
+        }
+        cursor.skip_whitespace();
+        let rest = cursor.rest().trim_end();
+        if !rest.is_empty() {
+            let kind = ErrorKind::UnexpectedToken {
+                expected: "the end of the line",
+            };
+            return Err(ParseError::at_token(kind, line, rest));
+        }
+        Ok(PresentBox::new([
+            dimensions[0],
+            dimensions[1],
+            dimensions[2],
+        ]))
+    }
+
+    /// Parses the dimension `token` and the unit that may follow it.
+    fn dimension<T: Dimension>(
+        &self,
+        line: &str,
+        token: &str,
+        cursor: &mut Cursor,
+        field: &'static str,
+    ) -> Result<T, ParseError> {
+        let digits = token.len() - token.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        let (number, suffix) = token.split_at(digits);
+        let (number, unit) = if !number.is_empty()
+            && !suffix.is_empty()
+            && suffix.chars().all(char::is_alphabetic)
+        {
+            let unit = Unit::from_suffix(suffix).ok_or_else(|| {
+                let kind = ErrorKind::UnexpectedToken {
+                    expected: "a unit: ft, in or cm",
+                };
+                ParseError::at_token(kind, line, suffix)
+            })?;
+            (number, Some(unit))
+        } else {
+            (token, cursor.unit())
+        };
+        if self.strict && number.len() > 1 && number.starts_with('0') {
+            let kind = ErrorKind::Invalid(format!("the {field} has a leading zero"));
+            return Err(ParseError::at_token(kind, line, number));
+        }
+        let value = parse::field(line, number, field)?;
+        match unit {
+            Some(unit) => unit.convert(value, self.unit).ok_or_else(|| {
+                let message = format!("the {field} is too large once converted to {}", self.unit);
+                ParseError::at_token(ErrorKind::Invalid(message), line, token)
+            }),
+            None => Ok(value),
+        }
+    }
+}
+
+/// Points at the first character that cannot appear in `LxWxH`.
+fn check_canonical(line: &str) -> Result<(), ParseError> {
+    match line
+        .char_indices()
+        .find(|&(_, c)| c != DELIMITER && !c.is_ascii_digit())
+    {
+        Some((i, c)) => Err(ParseError::at_token(
+            ErrorKind::UnexpectedChar(c),
+            line,
+            &line[i..i + c.len_utf8()],
+        )),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lenient(line: &str) -> Result<[u32; 3], ParseError> {
+        DimensionFormat::LENIENT
+            .parse(line)
+            .map(|present| present.ordered_dimensions)
+    }
+
+    fn missing(line: &str) -> &'static str {
+        match lenient(line).unwrap_err().kind {
+            ErrorKind::MissingToken { expected } => expected,
+            kind => panic!("unexpected {kind:?}"),
+        }
+    }
+
+    #[test]
+    fn test_separators() {
+        for line in [
+            "2x3x4",
+            " 2x3x4 ",
+            "2*3*4",
+            "2 × 3 × 4",
+            "2,3,4",
+            "2, 3, 4",
+            "2 3 4",
+            "2\t3 x 4",
+        ] {
+            assert_eq!(lenient(line), Ok([2, 3, 4]), "{line:?}");
+        }
+    }
+
+    #[test]
+    fn test_missing_dimensions() {
+        assert_eq!(missing("21x2x"), "a height");
+        assert_eq!(lenient("21x2x").unwrap_err().span, 5..5);
+        assert_eq!(missing("21"), "a width");
+        assert_eq!(missing(" 21x3 "), "a height");
+        assert_eq!(missing("x3x4"), "a length");
+        assert_eq!(missing(""), "a length");
+        assert_eq!(missing("2xx3"), "a width");
+        assert_eq!(missing("2 , x 3"), "a width");
+        let error = lenient("21x2x3x4").unwrap_err();
+        assert_eq!(error.span, 6..8);
+        assert_eq!(lenient("2 3 4 5").unwrap_err().span, 6..7);
+    }
+
+    #[test]
+    fn test_units() {
+        assert_eq!(lenient("2ftx3 ft x 4"), Ok([2, 3, 4]));
+        // 30cm is just under a foot, 13in just over
+        assert_eq!(lenient("12in 30cm 13 in"), Ok([1, 1, 2]));
+        let inches = DimensionFormat {
+            unit: Unit::Inches,
+            ..DimensionFormat::LENIENT
+        };
+        assert_eq!(
+            inches.parse::<u32>("1ft×2cm×3").unwrap().ordered_dimensions,
+            [1, 3, 12]
+        );
+        let error = lenient("2x3kgx4").unwrap_err();
+        assert_eq!(error.span, 3..5);
+        assert_eq!(
+            error.kind,
+            ErrorKind::UnexpectedToken {
+                expected: "a unit: ft, in or cm"
+            }
+        );
+        let error = inches.parse::<u8>("1x2x30ft").unwrap_err();
+        assert_eq!(
+            error.kind,
+            ErrorKind::Invalid("the height is too large once converted to inches".to_string())
+        );
+        assert_eq!(error.span, 4..8);
+    }
+
+    #[test]
+    fn test_invalid_numbers() {
+        let error = lenient("21x-2x22").unwrap_err();
+        assert!(matches!(
+            error.kind,
+            ErrorKind::InvalidField { field: "width", .. }
+        ));
+        assert_eq!(error.span, 3..5);
+        assert!(matches!(
+            lenient("2x3xin").unwrap_err().kind,
+            ErrorKind::InvalidField {
+                field: "height",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_strict() {
+        let strict = |line| DimensionFormat::STRICT.parse::<u32>(line);
+        assert_eq!(strict("2x30x4").unwrap().ordered_dimensions, [2, 4, 30]);
+        assert_eq!(strict("0x1x1").unwrap().ordered_dimensions, [0, 1, 1]);
+        for (line, c, column) in [
+            (" 2x3x4", ' ', 1),
+            ("2x3x4 ", ' ', 6),
+            ("2*3*4", '*', 2),
+            ("2x3x4in", 'i', 6),
+            ("2x+3x4", '+', 3),
+            ("2×3x4", '×', 2),
+        ] {
+            let error = strict(line).unwrap_err();
+            assert_eq!(error.kind, ErrorKind::UnexpectedChar(c), "{line:?}");
+            assert_eq!(error.column(), column, "{line:?}");
+        }
+        let error = strict("2x03x4").unwrap_err();
+        assert_eq!(error.span, 2..4);
+        assert_eq!(error.to_string(), "column 3: the width has a leading zero");
+        assert_eq!(
+            strict("2x3x").unwrap_err().kind,
+            ErrorKind::MissingToken {
+                expected: "a height"
+            }
+        );
+    }
+}
@@ -1,144 +1,235 @@
-const DELIMITER: char = 'x';
+pub mod format;
+pub mod packing;
+pub mod report;
+pub mod strategy;
 
-impl<'a> TryFrom<&'a str> for PresentBox {
-    type Error = String;
+use std::{fmt::Display, marker::PhantomData, num::ParseIntError, str::FromStr};
 
-    fn try_from(line: &'a str) -> Result<Self, Self::Error> {
-        let (dim1, dim2, dim3) = split_into_three(line)?;
-        let dim1 = dim1
-            .parse::<u32>()
-            .map_err(|e| format!("failed to parse {} into u32: {}", dim1, e))?;
-        let dim2 = dim2
-            .parse::<u32>()
-            .map_err(|e| format!("failed to parse {} into u32: {}", dim2, e))?;
-        let dim3 = dim3
-            .parse::<u32>()
-            .map_err(|e| format!("failed to parse {} into u32: {}", dim3, e))?;
-        Ok(PresentBox::new([dim1, dim2, dim3]))
-    }
-}
-
-trait SplitOnceNonEmpty<'a> {
-    fn split_once_non_empty(&self, delimiter: char) -> Option<(&'a str, &'a str)>;
-}
-
-impl<'a> SplitOnceNonEmpty<'a> for &'a str {
-    fn split_once_non_empty(&self, delimiter: char) -> Option<(&'a str, &'a str)> {
-        match self.split_once(delimiter) {
-            Some((prefix, suffix)) => {
-                if prefix.is_empty() || suffix.is_empty() {
-                    return None;
-                } else {
-                    return Some((prefix, suffix));
-                }
-            }
-            None => todo!(),
-        }
-    }
+use _2015::{ParseError, parse};
+use aoc_core::{Answer, Error, Solver};
+use format::DimensionFormat;
+use strategy::{Paper, Ribbon, RibbonStrategy, WrappingStrategy};
+
+/// An unsigned integer type dimensions are parsed into. Quantities are computed in `u128`,
+/// whatever the type.
+pub trait Dimension:
+    Copy + Ord + FromStr<Err = ParseIntError> + Into<u128> + TryFrom<u128>
+{
 }
 
-fn split_into_three(line: &str) -> Result<(&str, &str, &str), String> {
-    let split = line
-        .trim()
-        .split_once_non_empty(DELIMITER)
-        .and_then(|(part1, rest)| {
-            rest.split_once_non_empty(DELIMITER)
-                .map(|(part2, part3)| (part1, part2, part3))
-        });
-    let err = format!("Failed to split in exactly 3 parts: {line}");
-    match split {
-        Some(t) => Ok(t),
-        None => Err(err),
+impl<T> Dimension for T where
+    T: Copy + Ord + FromStr<Err = ParseIntError> + Into<u128> + TryFrom<u128>
+{
+}
+
+/// Parses `LxWxH` or one of the other formats accepted by `DimensionFormat::LENIENT`.
+impl<'a, T: Dimension> TryFrom<&'a str> for PresentBox<T> {
+    type Error = ParseError;
+
+    fn try_from(line: &'a str) -> Result<Self, Self::Error> {
+        DimensionFormat::LENIENT.parse(line)
     }
 }
 
 #[derive(Debug, PartialEq)]
-pub struct PresentBox {
+pub struct PresentBox<T = u32> {
     /// length, width, height, in ascending order
-    ordered_dimensions: [u32; 3],
+    ordered_dimensions: [T; 3],
 }
 
-impl PresentBox {
-    fn new(mut dimensions: [u32; 3]) -> PresentBox {
+impl<T: Dimension> PresentBox<T> {
+    fn new(mut dimensions: [T; 3]) -> PresentBox<T> {
         dimensions.sort();
         PresentBox {
             ordered_dimensions: dimensions,
         }
     }
-    fn area(&self) -> u32 {
-        self.ordered_dimensions[0] * self.ordered_dimensions[1] * 2
-            + self.ordered_dimensions[1] * self.ordered_dimensions[2] * 2
-            + self.ordered_dimensions[0] * self.ordered_dimensions[2] * 2
+    fn widened(&self) -> [u128; 3] {
+        self.ordered_dimensions.map(Into::into)
+    }
+    fn volume(&self) -> Option<u128> {
+        strategy::volume(self.widened())
     }
-    fn smallest_side_area(&self) -> u32 {
-        self.ordered_dimensions[0] * self.ordered_dimensions[1]
+    /// Square feet of wrapping paper needed under the puzzle rules, `None` if it does not
+    /// fit a `u128`.
+    pub fn wrap_qty(&self) -> Option<u128> {
+        self.paper_with(&Paper::default())
     }
-    fn smallest_perimeter(&self) -> u32 {
-        (self.ordered_dimensions[0] + self.ordered_dimensions[1]) * 2
+    /// Feet of ribbon needed under the puzzle rules, `None` if it does not fit a `u128`.
+    pub fn ribbon_qty(&self) -> Option<u128> {
+        self.ribbon_with(&Ribbon::default())
     }
-    fn volume(&self) -> u32 {
-        self.ordered_dimensions[0] * self.ordered_dimensions[1] * self.ordered_dimensions[2]
+    pub fn paper_with(&self, strategy: &impl WrappingStrategy) -> Option<u128> {
+        strategy.paper(self.widened())
     }
-    pub fn wrap_qty(&self) -> u32 {
-        self.area() + self.smallest_side_area()
+    pub fn ribbon_with(&self, strategy: &impl RibbonStrategy) -> Option<u128> {
+        strategy.ribbon(self.widened())
     }
-    pub fn ribbon_qty(&self) -> u32 {
-        self.smallest_perimeter() + self.volume()
+}
+
+/// A quantity of wrapping paper or ribbon too large for a `u128`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow {
+    /// what was being added up, as shown in messages
+    pub quantity: &'static str,
+    /// index of the present the total overflowed at
+    pub present: usize,
+}
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "the total {} overflows at present {}",
+            self.quantity,
+            self.present + 1
+        )
     }
 }
 
-pub struct PresentBoxIter<'a, I>
+impl std::error::Error for Overflow {}
+
+fn total<T: Dimension>(
+    presents: &[PresentBox<T>],
+    quantity: &'static str,
+    each: impl Fn(&PresentBox<T>) -> Option<u128>,
+) -> Result<u128, Overflow> {
+    presents
+        .iter()
+        .enumerate()
+        .try_fold(0u128, |total, (present, b)| {
+            each(b)
+                .and_then(|qty| total.checked_add(qty))
+                .ok_or(Overflow { quantity, present })
+        })
+}
+
+/// Square feet of wrapping paper needed for all `presents`.
+pub fn total_wrapping_paper<T: Dimension>(presents: &[PresentBox<T>]) -> Result<u128, Overflow> {
+    total_wrapping_paper_with(presents, &Paper::default())
+}
+
+/// Feet of ribbon needed for all `presents`.
+pub fn total_ribbon<T: Dimension>(presents: &[PresentBox<T>]) -> Result<u128, Overflow> {
+    total_ribbon_with(presents, &Ribbon::default())
+}
+
+pub fn total_wrapping_paper_with<T: Dimension>(
+    presents: &[PresentBox<T>],
+    strategy: &impl WrappingStrategy,
+) -> Result<u128, Overflow> {
+    total(presents, "wrapping paper", |present| {
+        present.paper_with(strategy)
+    })
+}
+
+pub fn total_ribbon_with<T: Dimension>(
+    presents: &[PresentBox<T>],
+    strategy: &impl RibbonStrategy,
+) -> Result<u128, Overflow> {
+    total(presents, "ribbon", |present| present.ribbon_with(strategy))
+}
+
+/// Totals past `u64::MAX` are given as text, as `Answer` holds no wider integer.
+fn answer(total: u128) -> Answer {
+    u64::try_from(total).map_or_else(|_| total.to_string().into(), Answer::from)
+}
+
+pub struct PresentBoxIter<'a, I, T = u32>
 where
     I: Iterator<Item = &'a str>,
 {
     lines: I,
+    format: DimensionFormat,
+    /// number of lines read so far
+    line: usize,
+    dimension: PhantomData<T>,
 }
 
-impl<'a, I> PresentBoxIter<'a, I>
+impl<'a, I, T> PresentBoxIter<'a, I, T>
 where
     I: Iterator<Item = &'a str>,
 {
     pub fn new(dimensions_in_order: I) -> Self {
         Self {
             lines: dimensions_in_order,
+            format: DimensionFormat::LENIENT,
+            line: 0,
+            dimension: PhantomData,
         }
     }
+
+    pub fn with_format(self, format: DimensionFormat) -> Self {
+        Self { format, ..self }
+    }
 }
 
-impl<'a, I> Iterator for PresentBoxIter<'a, I>
+impl<'a, I, T> Iterator for PresentBoxIter<'a, I, T>
 where
     I: Iterator<Item = &'a str>,
+    T: Dimension,
 {
-    type Item = Result<PresentBox, String>;
+    type Item = Result<PresentBox<T>, ParseError>;
 
+    /// Errors are located on their line, counting from the first line read.
     fn next(&mut self) -> Option<Self::Item> {
-        match self.lines.next() {
-            Some(line) => Some(PresentBox::try_from(line)),
-            None => None,
-        }
+        let line = self.lines.next()?;
+        self.line += 1;
+        Some(
+            self.format
+                .parse(line)
+                .map_err(|e| e.on_line(self.line, line)),
+        )
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// The presents of an order and their totals, which do not overflow.
+#[derive(Debug, PartialEq)]
+pub struct Order {
+    pub presents: Vec<PresentBox>,
+    /// square feet of wrapping paper for every present
+    pub wrapping_paper: u128,
+    /// feet of ribbon for every present
+    pub ribbon: u128,
+}
 
-    #[test]
-    fn test_split_into_three_valid_line() {
-        let expected = split_into_three("21x2x22");
-        assert_eq!(expected, Ok(("21", "2", "22")))
+pub struct Day02;
+
+impl Solver for Day02 {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 2;
+
+    type Input<'a> = Order;
+
+    /// Also fails if a total overflows, so that both parts can be answered.
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        let presents = parse::lines(input, PresentBox::try_from)?;
+        Ok(Order {
+            wrapping_paper: total_wrapping_paper(&presents)?,
+            ribbon: total_ribbon(&presents)?,
+            presents,
+        })
     }
 
-    #[test]
-    fn test_split_into_three_invalid_line_nan() {
-        let result = dbg!(split_into_three("21x2x"));
-        assert!(result.is_err());
-        assert!(result.unwrap_err().contains("Failed"));
+    fn part1(order: &Self::Input<'_>) -> Answer {
+        answer(order.wrapping_paper)
     }
 
+    fn part2(order: &Self::Input<'_>) -> Answer {
+        answer(order.ribbon)
+    }
+}
+
+aoc_core::examples!(Day02: small_box, long_box);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use _2015::ErrorKind;
+
     #[test]
     fn test_try_from_valid_numbers() {
-        let result = PresentBox::try_from("21x2x22").unwrap();
+        let result = PresentBox::<u32>::try_from("21x2x22").unwrap();
         assert_eq!(
             result,
             PresentBox {
@@ -147,25 +238,162 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_try_from_points_at_invalid_dimension() {
+        let error = PresentBox::<u32>::try_from("21x-2x22").unwrap_err();
+        assert!(matches!(
+            error.kind,
+            ErrorKind::InvalidField { field: "width", .. }
+        ));
+        assert_eq!(error.span, 3..5);
+        assert!(error.to_string().starts_with("column 4: invalid width"));
+    }
+
     #[test]
     fn test_area_calculation() {
-        let present = PresentBox::new([2, 3, 4]);
-        assert_eq!(present.area(), 52)
+        let present = PresentBox::<u32>::new([2, 3, 4]);
+        assert_eq!(strategy::surface_area(present.widened()), Some(52))
     }
 
     #[test]
     fn test_present_wrap_qty_calculation() {
-        let present1 = PresentBox::new([2, 3, 4]);
-        let present2 = PresentBox::new([1, 1, 10]);
-        assert_eq!(present1.wrap_qty(), 58);
-        assert_eq!(present2.wrap_qty(), 43);
+        let present1 = PresentBox::<u32>::new([2, 3, 4]);
+        let present2 = PresentBox::<u32>::new([1, 1, 10]);
+        assert_eq!(present1.wrap_qty(), Some(58));
+        assert_eq!(present2.wrap_qty(), Some(43));
     }
 
     #[test]
     fn test_present_ribbon_qty_calculation() {
-        let present1 = PresentBox::new([2, 3, 4]);
-        let present2 = PresentBox::new([1, 1, 10]);
-        assert_eq!(present1.ribbon_qty(), 34);
-        assert_eq!(present2.ribbon_qty(), 14);
+        let present1 = PresentBox::<u32>::new([2, 3, 4]);
+        let present2 = PresentBox::<u32>::new([1, 1, 10]);
+        assert_eq!(present1.ribbon_qty(), Some(34));
+        assert_eq!(present2.ribbon_qty(), Some(14));
+    }
+
+    #[test]
+    fn test_totals_with_strategies() {
+        use strategy::{Bow, Slack};
+        let presents = Day02::parse("2x3x4\n1x1x10").unwrap().presents;
+        let no_slack = Paper {
+            slack: Slack::Fixed(0),
+        };
+        assert_eq!(total_wrapping_paper_with(&presents, &no_slack), Ok(52 + 42));
+        let short_bow = Ribbon { bow: Bow::Fixed(1) };
+        assert_eq!(total_ribbon_with(&presents, &short_bow), Ok(11 + 5));
+        assert_eq!(total_ribbon_with(&presents, &strategy::volume), Ok(24 + 10));
+    }
+
+    #[test]
+    fn test_quantities_overflow() {
+        let present = PresentBox::<u64>::new([u64::MAX; 3]);
+        assert_eq!((present.wrap_qty(), present.ribbon_qty()), (None, None));
+        let present = PresentBox::<u32>::new([u32::MAX; 3]);
+        let max = u32::MAX as u128;
+        assert_eq!(present.wrap_qty(), Some(7 * max * max));
+        assert_eq!(present.ribbon_qty(), Some(4 * max + max * max * max));
+        assert!(u64::try_from(7 * max * max).is_err());
+    }
+
+    #[test]
+    fn test_totals_overflow() {
+        // each volume is 2^127, the second one overflows the total
+        let present = || PresentBox::<u64>::new([1 << 42, 1 << 42, 1 << 43]);
+        let presents = [present(), present()];
+        assert!(total_ribbon(&presents[..1]).is_ok());
+        let overflow = total_ribbon(&presents).unwrap_err();
+        assert_eq!(
+            overflow,
+            Overflow {
+                quantity: "ribbon",
+                present: 1
+            }
+        );
+        assert_eq!(
+            overflow.to_string(),
+            "the total ribbon overflows at present 2"
+        );
+        assert!(total_wrapping_paper(&presents).is_ok());
+        let presents = [present(), PresentBox::new([u64::MAX; 3])];
+        assert_eq!(total_wrapping_paper(&presents).unwrap_err().present, 1);
+    }
+
+    #[test]
+    fn test_large_totals() {
+        let line = format!("{0}x{0}x{0}", u32::MAX);
+        let order = Day02::parse(&line).unwrap();
+        let max = u32::MAX as u128;
+        assert_eq!(
+            Day02::part1(&order),
+            Answer::from((7 * max * max).to_string())
+        );
+        assert_eq!(
+            Day02::part2(&order).to_string(),
+            (4 * max + max * max * max).to_string()
+        );
+    }
+
+    #[test]
+    fn test_iter_with_format() {
+        let lines = "2x3x4\n2 3 4\n";
+        let presents: Result<Vec<PresentBox>, _> = PresentBoxIter::new(lines.lines()).collect();
+        assert_eq!(presents.unwrap().len(), 2);
+        let error = PresentBoxIter::<_, u32>::new(lines.lines())
+            .with_format(DimensionFormat::STRICT)
+            .find_map(Result::err)
+            .unwrap();
+        assert_eq!(error.line, Some(2));
+        assert_eq!(error.kind, ErrorKind::UnexpectedChar(' '));
+    }
+
+    #[test]
+    fn test_solver() {
+        let order = Day02::parse("2x3x4\n1x1x10").unwrap();
+        assert_eq!(order.presents.len(), 2);
+        assert_eq!((order.wrapping_paper, order.ribbon), (58 + 43, 34 + 14));
+        assert_eq!(Day02::part1(&order), Answer::from(58u32 + 43));
+        assert_eq!(Day02::part2(&order), Answer::from(34u32 + 14));
+    }
+}
+
+#[cfg(test)]
+mod properties {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn parsing_never_panics(line in "\\PC*") {
+            let _ = PresentBox::<u32>::try_from(line.as_str());
+        }
+
+        #[test]
+        fn parsing_box_like_lines_never_panics(line in "[0-9x \\t-]{0,20}") {
+            if let Err(error) = PresentBox::<u8>::try_from(line.as_str()) {
+                prop_assert!(error.span.start <= error.span.end);
+                prop_assert!(error.span.end <= line.len());
+                prop_assert!(line.is_char_boundary(error.span.start));
+            }
+        }
+
+        #[test]
+        fn valid_lines_parse(l: u32, w: u32, h: u32) {
+            let present = PresentBox::<u32>::try_from(format!("{l}x{w}x{h}").as_str()).unwrap();
+            let mut dimensions = [l, w, h];
+            dimensions.sort();
+            prop_assert_eq!(present.ordered_dimensions, dimensions);
+        }
+
+        #[test]
+        fn quantities_are_exact_or_none(l: u64, w: u64, h: u64) {
+            let present = PresentBox::new([l, w, h]);
+            let [a, b, c] = present.widened();
+            let volume = a.checked_mul(b).and_then(|ab| ab.checked_mul(c));
+            prop_assert_eq!(present.volume(), volume);
+            if let (Some(volume), Some(ribbon)) = (volume, present.ribbon_qty()) {
+                prop_assert_eq!(ribbon, 2 * (a + b) + volume);
+            }
+            let _ = present.wrap_qty();
+        }
     }
 }
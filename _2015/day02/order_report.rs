@@ -0,0 +1,37 @@
+//! Prints a breakdown of an order of presents, as text, CSV or JSON:
+//!
+//! ```sh
+//! cargo run --bin _2015_day02_report -- --csv [input]
+//! ```
+
+use std::io::Write;
+
+use _2015_day02::{Day02, PresentBoxIter, report::OrderReport};
+use aoc_core::{Solver, input};
+
+const USAGE: &str = "usage: _2015_day02_report [--csv | --json] [<input>]";
+
+fn main() -> Result<(), aoc_core::Error> {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let format = match args.first() {
+        Some(arg) if arg.starts_with("--") => Some(args.remove(0)),
+        _ => None,
+    };
+    if args.len() > 1 {
+        return Err(USAGE.into());
+    }
+    let input_file_content =
+        input::read(Day02::YEAR, Day02::DAY, args.first().map(String::as_str))?;
+    let report: OrderReport = OrderReport::new(PresentBoxIter::new(input_file_content.lines()))?;
+    let mut out = std::io::stdout().lock();
+    match format.as_deref() {
+        Some("--csv") => report.write_csv(&mut out)?,
+        Some("--json") => {
+            report.write_json(&mut out)?;
+            writeln!(out)?;
+        }
+        Some(_) => return Err(USAGE.into()),
+        None => writeln!(out, "{report}")?,
+    }
+    Ok(())
+}
@@ -0,0 +1,273 @@
+//! Packs presents into shipping crates with a first-fit-decreasing heuristic.
+//!
+//! Presents are taken from the largest volume to the smallest and put in the first open
+//! crate with a free space they fit in, turned whichever way is needed. A present fitting
+//! none of them opens the smallest crate it fits in. The space left around a present is
+//! cut into three boxes: beside it, behind it and above it.
+
+use crate::{Dimension, PresentBox};
+
+/// A present in a crate, turned so that its sides line up with the sides of the crate.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Placement {
+    /// index of the present in the packed list
+    pub present: usize,
+    /// corner of the present closest to the corner of the crate
+    pub position: [u128; 3],
+    /// size of the present along each side of the crate
+    pub size: [u128; 3],
+}
+
+/// An empty box within a crate.
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct Space {
+    position: [u128; 3],
+    size: [u128; 3],
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct PackedCrate {
+    pub size: [u128; 3],
+    pub placements: Vec<Placement>,
+    free: Vec<Space>,
+}
+
+fn volume(size: [u128; 3]) -> f64 {
+    size.iter().map(|&side| side as f64).product()
+}
+
+fn fill_ratio(used: f64, total: f64) -> f64 {
+    if total == 0.0 { 0.0 } else { used / total }
+}
+
+/// The distinct ways of turning a box of `size` so that its sides line up with a crate's.
+fn rotations([a, b, c]: [u128; 3]) -> Vec<[u128; 3]> {
+    let mut rotations = Vec::with_capacity(6);
+    for rotation in [
+        [a, b, c],
+        [a, c, b],
+        [b, a, c],
+        [b, c, a],
+        [c, a, b],
+        [c, b, a],
+    ] {
+        if !rotations.contains(&rotation) {
+            rotations.push(rotation);
+        }
+    }
+    rotations
+}
+
+fn fits(size: [u128; 3], within: [u128; 3]) -> bool {
+    size.iter().zip(within).all(|(&side, room)| side <= room)
+}
+
+impl PackedCrate {
+    fn new(size: [u128; 3]) -> PackedCrate {
+        PackedCrate {
+            size,
+            placements: Vec::new(),
+            free: vec![Space {
+                position: [0; 3],
+                size,
+            }],
+        }
+    }
+
+    /// Puts the present in the first free space it fits in, if there is one.
+    fn place(&mut self, present: usize, size: [u128; 3]) -> bool {
+        let rotations = rotations(size);
+        let found = self.free.iter().enumerate().find_map(|(i, space)| {
+            let rotation = rotations.iter().find(|r| fits(**r, space.size))?;
+            Some((i, *rotation))
+        });
+        let Some((i, size)) = found else {
+            return false;
+        };
+        let Space {
+            position: [x, y, z],
+            size: [sx, sy, sz],
+        } = self.free[i];
+        let [bx, by, bz] = size;
+        let left = [
+            Space {
+                position: [x + bx, y, z],
+                size: [sx - bx, sy, sz],
+            },
+            Space {
+                position: [x, y + by, z],
+                size: [bx, sy - by, sz],
+            },
+            Space {
+                position: [x, y, z + bz],
+                size: [bx, by, sz - bz],
+            },
+        ];
+        // the spaces left take the place of the one used, to fill crates corner first
+        self.free.splice(
+            i..=i,
+            left.into_iter().filter(|space| !space.size.contains(&0)),
+        );
+        self.placements.push(Placement {
+            present,
+            position: [x, y, z],
+            size,
+        });
+        true
+    }
+
+    /// The share of the crate taken by presents, from 0 to 1.
+    pub fn fill_ratio(&self) -> f64 {
+        let used = self.placements.iter().map(|p| volume(p.size)).sum();
+        fill_ratio(used, volume(self.size))
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Packing {
+    pub crates: Vec<PackedCrate>,
+    /// indices of the presents too large for any crate
+    pub unpacked: Vec<usize>,
+}
+
+impl Packing {
+    pub fn crate_count(&self) -> usize {
+        self.crates.len()
+    }
+
+    /// The share of all crates taken by presents, from 0 to 1.
+    pub fn fill_ratio(&self) -> f64 {
+        let used = self.crates.iter().flat_map(|c| &c.placements);
+        let used = used.map(|p| volume(p.size)).sum();
+        fill_ratio(used, self.crates.iter().map(|c| volume(c.size)).sum())
+    }
+}
+
+/// Packs `presents` into crates of the given `sizes`, as many of each as needed.
+pub fn pack<T: Dimension>(presents: &[PresentBox<T>], sizes: &[[u128; 3]]) -> Packing {
+    let mut sizes = sizes.to_vec();
+    sizes.sort_by(|a, b| volume(*a).total_cmp(&volume(*b)));
+    let mut order: Vec<(usize, [u128; 3])> = presents
+        .iter()
+        .map(PresentBox::widened)
+        .enumerate()
+        .collect();
+    // a stable sort keeps equal presents in their order
+    order.sort_by(|(_, a), (_, b)| volume(*b).total_cmp(&volume(*a)));
+
+    let mut packing = Packing::default();
+    for (present, size) in order {
+        if packing
+            .crates
+            .iter_mut()
+            .any(|packed| packed.place(present, size))
+        {
+            continue;
+        }
+        let ordered = |mut sides: [u128; 3]| {
+            sides.sort();
+            sides
+        };
+        match sizes.iter().find(|s| fits(size, ordered(**s))) {
+            Some(&crate_size) => {
+                let mut packed = PackedCrate::new(crate_size);
+                assert!(packed.place(present, size), "the present fits the crate");
+                packing.crates.push(packed);
+            }
+            None => packing.unpacked.push(present),
+        }
+    }
+    packing.unpacked.sort();
+    packing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use _2015::random::Xorshift;
+
+    fn presents(dimensions: &[[u32; 3]]) -> Vec<PresentBox> {
+        dimensions.iter().map(|d| PresentBox::new(*d)).collect()
+    }
+
+    /// Checks that every present is packed once, inside its crate and apart from the others.
+    fn assert_valid(packing: &Packing, count: usize) {
+        let mut packed: Vec<usize> = packing.unpacked.clone();
+        for packed_crate in &packing.crates {
+            let placements = &packed_crate.placements;
+            for (i, p) in placements.iter().enumerate() {
+                packed.push(p.present);
+                for axis in 0..3 {
+                    assert!(p.position[axis] + p.size[axis] <= packed_crate.size[axis]);
+                }
+                for q in &placements[i + 1..] {
+                    let apart = (0..3).any(|axis| {
+                        p.position[axis] + p.size[axis] <= q.position[axis]
+                            || q.position[axis] + q.size[axis] <= p.position[axis]
+                    });
+                    assert!(apart, "{p:?} overlaps {q:?}");
+                }
+            }
+        }
+        packed.sort();
+        assert_eq!(packed, (0..count).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_fills_a_crate() {
+        let cubes = presents(&[[1, 1, 1]; 8]);
+        let packing = pack(&cubes, &[[2, 2, 2]]);
+        assert_valid(&packing, 8);
+        assert_eq!(packing.crate_count(), 1);
+        assert_eq!(packing.fill_ratio(), 1.0);
+    }
+
+    #[test]
+    fn test_largest_first() {
+        let mut order = presents(&[[1, 1, 1]; 8]);
+        order.push(PresentBox::new([2, 2, 2]));
+        let packing = pack(&order, &[[2, 2, 3]]);
+        assert_valid(&packing, 9);
+        assert_eq!(packing.crate_count(), 2);
+        assert_eq!(packing.crates[0].placements[0].present, 8);
+        assert_eq!(packing.crates[0].fill_ratio(), 1.0);
+        assert_eq!(packing.fill_ratio(), 16.0 / 24.0);
+    }
+
+    #[test]
+    fn test_turns_presents() {
+        let packing = pack(&presents(&[[1, 3, 1], [2, 1, 3]]), &[[3, 1, 3]]);
+        assert_valid(&packing, 2);
+        assert_eq!(packing.crate_count(), 1);
+        // the larger present goes first, turned to stand on its side
+        assert_eq!(packing.crates[0].placements[0].size, [2, 1, 3]);
+        assert_eq!(packing.crates[0].fill_ratio(), 1.0);
+    }
+
+    #[test]
+    fn test_crate_sizes() {
+        let order = presents(&[[1, 1, 1], [5, 5, 5], [3, 3, 3]]);
+        let packing = pack(&order, &[[4, 4, 4], [1, 2, 1]]);
+        assert_valid(&packing, 3);
+        assert_eq!(packing.unpacked, [1]);
+        let sizes: Vec<[u128; 3]> = packing.crates.iter().map(|c| c.size).collect();
+        assert_eq!(sizes, [[4, 4, 4]]);
+        let packing = pack(&presents(&[[1, 1, 1]]), &[[4, 4, 4], [1, 2, 1]]);
+        assert_eq!(packing.crates[0].size, [1, 2, 1]);
+        assert_eq!(packing.fill_ratio(), 0.5);
+        assert_eq!(pack::<u32>(&[], &[[1, 1, 1]]).fill_ratio(), 0.0);
+    }
+
+    #[test]
+    fn test_many_presents() {
+        let mut random = Xorshift::new(0x2015_0202);
+        let mut side = || random.below(6) as u32 + 1;
+        let order: Vec<PresentBox> = (0..300)
+            .map(|_| PresentBox::new([side(), side(), side()]))
+            .collect();
+        let packing = pack(&order, &[[10, 10, 10], [6, 6, 12]]);
+        assert_valid(&packing, order.len());
+        assert!(packing.unpacked.is_empty());
+        assert!(packing.fill_ratio() > 0.5, "{}", packing.fill_ratio());
+    }
+}
@@ -1,13 +1,13 @@
-use _2015_day02::PresentBoxIter;
+use _2015_day02::Day02;
+use aoc_core::{Solver, input};
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let input_file_content = std::fs::read_to_string("_2015/day02/input1.txt")?;
-    let presents = PresentBoxIter::new(input_file_content.lines());
-    let mut total_wrapping_paper: u32 = 0;
-    for present in presents {
-        let present = present?;
-        total_wrapping_paper += present.wrap_qty();
-    }
-    println!("total square feet of wrapping paper: {}", total_wrapping_paper);
+fn main() -> Result<(), aoc_core::Error> {
+    let input_file_content =
+        input::read(Day02::YEAR, Day02::DAY, std::env::args().nth(1).as_deref())?;
+    let order = Day02::parse(&input_file_content)?;
+    println!(
+        "total square feet of wrapping paper: {}",
+        Day02::part1(&order)
+    );
     Ok(())
-}
\ No newline at end of file
+}
@@ -1,13 +1,10 @@
-use _2015_day02::PresentBoxIter;
+use _2015_day02::Day02;
+use aoc_core::{Solver, input};
 
-fn main() -> Result<(), Box<dyn std::error::Error>>{
-    let input_file_content = std::fs::read_to_string("_2015/day02/input1.txt")?;
-    let present_box_iter = PresentBoxIter::new(input_file_content.lines());
-    let mut total_ribbon: u32 = 0;
-    for present in present_box_iter {
-        let present = present?;
-        total_ribbon += present.ribbon_qty()
-    }
-    println!("total feet of ribbon: {}", total_ribbon);
+fn main() -> Result<(), aoc_core::Error> {
+    let input_file_content =
+        input::read(Day02::YEAR, Day02::DAY, std::env::args().nth(1).as_deref())?;
+    let order = Day02::parse(&input_file_content)?;
+    println!("total feet of ribbon: {}", Day02::part2(&order));
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,312 @@
+use std::{collections::BTreeMap, fmt::Display, io::Write};
+
+use _2015::ParseError;
+use aoc_core::Error;
+use serde::Serialize;
+
+use crate::{Dimension, Overflow, PresentBox};
+
+/// One present of an order and what it needs.
+#[derive(Serialize, PartialEq, Eq, Debug, Clone)]
+pub struct PresentRow<T> {
+    /// 1-based position of the present in the order
+    pub line: usize,
+    /// dimensions in ascending order, whatever order they were given in
+    pub dimensions: [T; 3],
+    pub volume: u128,
+    pub paper: u128,
+    pub ribbon: u128,
+}
+
+/// The number of presents whose volume is within `min..=max`.
+#[derive(Serialize, PartialEq, Eq, Debug, Clone)]
+pub struct VolumeBucket {
+    pub min: u128,
+    pub max: u128,
+    pub count: usize,
+}
+
+/// Presents of the same size, turned around or not.
+#[derive(Serialize, PartialEq, Eq, Debug, Clone)]
+pub struct Duplicate<T> {
+    pub dimensions: [T; 3],
+    pub lines: Vec<usize>,
+}
+
+/// A breakdown of a whole order of presents.
+///
+/// Exported as CSV, one row per present, or as JSON, with the summary as well.
+#[derive(Serialize, PartialEq, Eq, Debug)]
+pub struct OrderReport<T = u32> {
+    pub boxes: usize,
+    pub total_paper: u128,
+    pub total_ribbon: u128,
+    /// the present with the largest volume, the first one on ties
+    pub largest: Option<PresentRow<T>>,
+    /// the present with the smallest volume, the first one on ties
+    pub smallest: Option<PresentRow<T>>,
+    /// presents per power of two of their volume, empty buckets are left out
+    pub volume_histogram: Vec<VolumeBucket>,
+    /// sizes ordered more than once, by first occurrence
+    pub duplicates: Vec<Duplicate<T>>,
+    pub presents: Vec<PresentRow<T>>,
+}
+
+/// The volumes of the histogram bucket `volume` falls in, `0` has a bucket of its own.
+fn bucket(volume: u128) -> (u128, u128) {
+    match volume {
+        0 => (0, 0),
+        _ => {
+            let min = 1 << (u128::BITS - 1 - volume.leading_zeros());
+            (min, min | (min - 1))
+        }
+    }
+}
+
+impl<T: Dimension> PresentRow<T> {
+    fn new(line: usize, present: &PresentBox<T>) -> Result<PresentRow<T>, Overflow> {
+        let overflow = |quantity| Overflow {
+            quantity,
+            present: line - 1,
+        };
+        Ok(PresentRow {
+            line,
+            dimensions: present.ordered_dimensions,
+            volume: present.volume().ok_or(overflow("volume"))?,
+            paper: present.wrap_qty().ok_or(overflow("wrapping paper"))?,
+            ribbon: present.ribbon_qty().ok_or(overflow("ribbon"))?,
+        })
+    }
+}
+
+impl<T: Dimension> OrderReport<T> {
+    /// Reports on the presents read by a `PresentBoxIter`, failing on the first one that
+    /// cannot be parsed or whose quantities overflow.
+    pub fn new<I>(presents: I) -> Result<OrderReport<T>, Error>
+    where
+        I: IntoIterator<Item = Result<PresentBox<T>, ParseError>>,
+    {
+        let mut rows: Vec<PresentRow<T>> = Vec::new();
+        let (mut total_paper, mut total_ribbon) = (0u128, 0u128);
+        let mut histogram = BTreeMap::new();
+        let mut sizes: BTreeMap<[T; 3], Vec<usize>> = BTreeMap::new();
+        for (i, present) in presents.into_iter().enumerate() {
+            let row = PresentRow::new(i + 1, &present?)?;
+            let overflow = |quantity| Overflow {
+                quantity,
+                present: i,
+            };
+            total_paper = total_paper
+                .checked_add(row.paper)
+                .ok_or(overflow("wrapping paper"))?;
+            total_ribbon = total_ribbon
+                .checked_add(row.ribbon)
+                .ok_or(overflow("ribbon"))?;
+            *histogram.entry(bucket(row.volume)).or_default() += 1;
+            sizes.entry(row.dimensions).or_default().push(row.line);
+            rows.push(row);
+        }
+
+        let mut duplicates: Vec<Duplicate<T>> = sizes
+            .into_iter()
+            .filter(|(_, lines)| lines.len() > 1)
+            .map(|(dimensions, lines)| Duplicate { dimensions, lines })
+            .collect();
+        duplicates.sort_by_key(|duplicate| duplicate.lines[0]);
+        // `max_by_key` keeps the last of equal elements, hence the reversed key
+        let largest = rows
+            .iter()
+            .min_by_key(|row| std::cmp::Reverse(row.volume))
+            .cloned();
+        let smallest = rows.iter().min_by_key(|row| row.volume).cloned();
+        Ok(OrderReport {
+            boxes: rows.len(),
+            total_paper,
+            total_ribbon,
+            largest,
+            smallest,
+            volume_histogram: histogram
+                .into_iter()
+                .map(|((min, max), count)| VolumeBucket { min, max, count })
+                .collect(),
+            duplicates,
+            presents: rows,
+        })
+    }
+}
+
+impl<T: Dimension + Display> OrderReport<T> {
+    /// Writes one row per present, with a header.
+    pub fn write_csv(&self, mut out: impl Write) -> std::io::Result<()> {
+        writeln!(out, "line,shortest,middle,longest,volume,paper,ribbon")?;
+        for row in &self.presents {
+            let [a, b, c] = row.dimensions;
+            writeln!(
+                out,
+                "{},{a},{b},{c},{},{},{}",
+                row.line, row.volume, row.paper, row.ribbon
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Serialize> OrderReport<T> {
+    pub fn write_json(&self, out: impl Write) -> serde_json::Result<()> {
+        serde_json::to_writer_pretty(out, self)
+    }
+}
+
+fn size<T: Display>([a, b, c]: &[T; 3]) -> String {
+    format!("{a}x{b}x{c}")
+}
+
+impl<T: Display> Display for PresentRow<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} on line {}, volume {}",
+            size(&self.dimensions),
+            self.line,
+            self.volume
+        )
+    }
+}
+
+impl<T: Display> Display for OrderReport<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "presents: {}", self.boxes)?;
+        writeln!(
+            f,
+            "total square feet of wrapping paper: {}",
+            self.total_paper
+        )?;
+        writeln!(f, "total feet of ribbon: {}", self.total_ribbon)?;
+        if let (Some(largest), Some(smallest)) = (&self.largest, &self.smallest) {
+            writeln!(f, "largest: {largest}")?;
+            writeln!(f, "smallest: {smallest}")?;
+        }
+        writeln!(f, "volumes:")?;
+        for bucket in &self.volume_histogram {
+            let range = format!("{}..={}", bucket.min, bucket.max);
+            writeln!(f, "  {range:>24} {}", bucket.count)?;
+        }
+        write!(f, "duplicates: {}", self.duplicates.len())?;
+        for duplicate in &self.duplicates {
+            let lines: Vec<String> = duplicate.lines.iter().map(usize::to_string).collect();
+            write!(
+                f,
+                "\n  {} on lines {}",
+                size(&duplicate.dimensions),
+                lines.join(", ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PresentBoxIter;
+
+    const ORDER: &str = "2x3x4\n1x1x10\n4x3x2\n0x5x5\n2x4x3";
+
+    fn report(order: &str) -> Result<OrderReport, Error> {
+        OrderReport::new(PresentBoxIter::new(order.lines()))
+    }
+
+    #[test]
+    fn test_summary() {
+        let report = report(ORDER).unwrap();
+        assert_eq!(report.boxes, 5);
+        assert_eq!(report.total_paper, 58 * 3 + 43 + 50);
+        assert_eq!(report.total_ribbon, 34 * 3 + 14 + 10);
+        assert_eq!(report.largest.unwrap().line, 1);
+        assert_eq!(report.smallest.unwrap().dimensions, [0, 5, 5]);
+        assert_eq!(
+            report.volume_histogram,
+            [
+                VolumeBucket {
+                    min: 0,
+                    max: 0,
+                    count: 1
+                },
+                VolumeBucket {
+                    min: 8,
+                    max: 15,
+                    count: 1
+                },
+                VolumeBucket {
+                    min: 16,
+                    max: 31,
+                    count: 3
+                },
+            ]
+        );
+        assert_eq!(
+            report.duplicates,
+            [Duplicate {
+                dimensions: [2, 3, 4],
+                lines: vec![1, 3, 5]
+            }]
+        );
+    }
+
+    #[test]
+    fn test_empty_order() {
+        let report = report("").unwrap();
+        assert_eq!((report.boxes, report.total_paper), (0, 0));
+        assert_eq!((report.largest, report.smallest), (None, None));
+        assert!(report.volume_histogram.is_empty());
+    }
+
+    #[test]
+    fn test_buckets() {
+        assert_eq!(bucket(1), (1, 1));
+        assert_eq!(bucket(24), (16, 31));
+        assert_eq!(bucket(u128::MAX), (1 << 127, u128::MAX));
+    }
+
+    #[test]
+    fn test_csv() {
+        let mut csv = Vec::new();
+        report("2x3x4\n10x1x1")
+            .unwrap()
+            .write_csv(&mut csv)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "line,shortest,middle,longest,volume,paper,ribbon\n\
+             1,2,3,4,24,58,34\n\
+             2,1,1,10,10,43,14\n"
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let mut json = Vec::new();
+        report(ORDER).unwrap().write_json(&mut json).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(json["boxes"], 5);
+        assert_eq!(json["largest"]["dimensions"], serde_json::json!([2, 3, 4]));
+        assert_eq!(json["duplicates"][0]["lines"], serde_json::json!([1, 3, 5]));
+        assert_eq!(json["presents"][1]["ribbon"], 14);
+    }
+
+    #[test]
+    fn test_text() {
+        let text = report(ORDER).unwrap().to_string();
+        assert!(text.contains("largest: 2x3x4 on line 1, volume 24\n"));
+        assert!(text.ends_with("duplicates: 1\n  2x3x4 on lines 1, 3, 5"));
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = report("2x3x4\n2xx4").unwrap_err();
+        assert!(error.to_string().starts_with("line 2"), "{error}");
+        let present = || PresentBox::<u64>::new([1 << 42, 1 << 42, 1 << 43]);
+        let error = OrderReport::new([Ok(present()), Ok(present())]).unwrap_err();
+        assert_eq!(error.to_string(), "the total ribbon overflows at present 2");
+    }
+}
@@ -0,0 +1,188 @@
+//! How much paper and ribbon a present needs, from its dimensions in ascending order and
+//! widened to `u128`. Every quantity is `None` when it does not fit a `u128`.
+//!
+//! `Paper::default()` and `Ribbon::default()` follow the rules of the puzzle. Closures
+//! taking the dimensions can be used as strategies too, with the helpers of this module.
+
+/// Square feet of wrapping paper needed for a box.
+pub trait WrappingStrategy {
+    fn paper(&self, dimensions: [u128; 3]) -> Option<u128>;
+}
+
+/// Feet of ribbon needed for a box, bow included.
+pub trait RibbonStrategy {
+    fn ribbon(&self, dimensions: [u128; 3]) -> Option<u128>;
+}
+
+impl<F: Fn([u128; 3]) -> Option<u128>> WrappingStrategy for F {
+    fn paper(&self, dimensions: [u128; 3]) -> Option<u128> {
+        self(dimensions)
+    }
+}
+
+impl<F: Fn([u128; 3]) -> Option<u128>> RibbonStrategy for F {
+    fn ribbon(&self, dimensions: [u128; 3]) -> Option<u128> {
+        self(dimensions)
+    }
+}
+
+pub fn surface_area([a, b, c]: [u128; 3]) -> Option<u128> {
+    let sides = a
+        .checked_mul(b)?
+        .checked_add(b.checked_mul(c)?)?
+        .checked_add(a.checked_mul(c)?)?;
+    sides.checked_mul(2)
+}
+
+pub fn smallest_side_area([a, b, _]: [u128; 3]) -> Option<u128> {
+    a.checked_mul(b)
+}
+
+pub fn smallest_perimeter([a, b, _]: [u128; 3]) -> Option<u128> {
+    a.checked_add(b)?.checked_mul(2)
+}
+
+pub fn volume([a, b, c]: [u128; 3]) -> Option<u128> {
+    a.checked_mul(b)?.checked_mul(c)
+}
+
+/// How a fraction of a foot is rounded to a whole one.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Rounding {
+    #[default]
+    Up,
+    Down,
+    /// half a foot is rounded up
+    Nearest,
+}
+
+impl Rounding {
+    /// `percent` of `quantity`, rounded.
+    fn percent_of(self, quantity: u128, percent: u32) -> Option<u128> {
+        let scaled = quantity.checked_mul(percent.into())?;
+        let (whole, rest) = (scaled / 100, scaled % 100);
+        let round_up = match self {
+            Rounding::Up => rest > 0,
+            Rounding::Down => false,
+            Rounding::Nearest => rest >= 50,
+        };
+        Some(whole + u128::from(round_up))
+    }
+}
+
+/// Paper added to the surface of the box.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Slack {
+    /// the area of the smallest side, as in the puzzle
+    #[default]
+    SmallestSide,
+    /// the same amount for every box, in square feet
+    Fixed(u128),
+    /// a share of the surface area
+    Percent { percent: u32, rounding: Rounding },
+}
+
+/// Covers the surface of the box, plus some slack.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Paper {
+    pub slack: Slack,
+}
+
+impl WrappingStrategy for Paper {
+    fn paper(&self, dimensions: [u128; 3]) -> Option<u128> {
+        let surface = surface_area(dimensions)?;
+        let slack = match self.slack {
+            Slack::SmallestSide => smallest_side_area(dimensions)?,
+            Slack::Fixed(feet) => feet,
+            Slack::Percent { percent, rounding } => rounding.percent_of(surface, percent)?,
+        };
+        surface.checked_add(slack)
+    }
+}
+
+/// Ribbon tied into the bow.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Bow {
+    /// as many feet as the volume in cubic feet, as in the puzzle
+    #[default]
+    Volume,
+    /// the same length for every box, in feet
+    Fixed(u128),
+    /// a share of the ribbon going around the box
+    Percent { percent: u32, rounding: Rounding },
+}
+
+/// Goes around the smallest perimeter of the box, plus a bow.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Ribbon {
+    pub bow: Bow,
+}
+
+impl RibbonStrategy for Ribbon {
+    fn ribbon(&self, dimensions: [u128; 3]) -> Option<u128> {
+        let around = smallest_perimeter(dimensions)?;
+        let bow = match self.bow {
+            Bow::Volume => volume(dimensions)?,
+            Bow::Fixed(feet) => feet,
+            Bow::Percent { percent, rounding } => rounding.percent_of(around, percent)?,
+        };
+        around.checked_add(bow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_puzzle_rules() {
+        assert_eq!(Paper::default().paper([2, 3, 4]), Some(58));
+        assert_eq!(Ribbon::default().ribbon([1, 1, 10]), Some(14));
+    }
+
+    #[test]
+    fn test_slack_and_bow() {
+        let paper = |slack| Paper { slack }.paper([2, 3, 4]);
+        assert_eq!(paper(Slack::Fixed(0)), Some(52));
+        let percent = |percent, rounding| paper(Slack::Percent { percent, rounding });
+        // 10% of 52 is 5.2
+        assert_eq!(percent(10, Rounding::Up), Some(58));
+        assert_eq!(percent(10, Rounding::Down), Some(57));
+        assert_eq!(percent(10, Rounding::Nearest), Some(57));
+        assert_eq!(percent(0, Rounding::Up), Some(52));
+
+        let ribbon = |bow| Ribbon { bow }.ribbon([1, 1, 10]);
+        assert_eq!(ribbon(Bow::Fixed(3)), Some(7));
+        // 50% of 4 is 2, 25% of 4 is 1, 12% of 4 is 0.48
+        let percent = |percent, rounding| ribbon(Bow::Percent { percent, rounding });
+        assert_eq!(percent(50, Rounding::Down), Some(6));
+        assert_eq!(percent(25, Rounding::Up), Some(5));
+        assert_eq!(percent(12, Rounding::Nearest), Some(4));
+        assert_eq!(percent(13, Rounding::Nearest), Some(5));
+    }
+
+    #[test]
+    fn test_closures() {
+        let gift_bag = |_: [u128; 3]| Some(1);
+        assert_eq!(gift_bag.paper([2, 3, 4]), Some(1));
+        let twice_around = |d: [u128; 3]| smallest_perimeter(d)?.checked_mul(2);
+        assert_eq!(twice_around.ribbon([2, 3, 4]), Some(20));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let huge = [u128::MAX; 3];
+        assert_eq!(
+            Paper {
+                slack: Slack::Fixed(0)
+            }
+            .paper(huge),
+            None
+        );
+        let paper = Paper {
+            slack: Slack::Fixed(u128::MAX),
+        };
+        assert_eq!(paper.paper([1, 1, 1]), None);
+        assert_eq!(Rounding::Up.percent_of(u128::MAX, 2), None);
+    }
+}
@@ -0,0 +1,131 @@
+use std::borrow::Cow;
+
+use _2015::{ErrorKind, Point};
+
+/// The characters directions are written with and the step each of them stands for.
+///
+/// A later mapping of a character replaces an earlier one.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Alphabet {
+    moves: Cow<'static, [(char, Point)]>,
+}
+
+const NORTH: Point = Point::new(0, 1);
+const SOUTH: Point = Point::new(0, -1);
+const EAST: Point = Point::new(1, 0);
+const WEST: Point = Point::new(-1, 0);
+
+impl Alphabet {
+    /// `^`, `v`, `>` and `<`, the directions of the puzzle.
+    pub const CARDINAL: Alphabet = Alphabet {
+        moves: Cow::Borrowed(&[('^', NORTH), ('v', SOUTH), ('>', EAST), ('<', WEST)]),
+    };
+
+    /// The directions of the puzzle, and diagonals laid out as on a numeric keypad:
+    /// `7` north west, `9` north east, `1` south west and `3` south east.
+    pub const EIGHT_WAY: Alphabet = Alphabet {
+        moves: Cow::Borrowed(&[
+            ('^', NORTH),
+            ('v', SOUTH),
+            ('>', EAST),
+            ('<', WEST),
+            ('7', Point::new(-1, 1)),
+            ('9', Point::new(1, 1)),
+            ('1', Point::new(-1, -1)),
+            ('3', Point::new(1, -1)),
+        ]),
+    };
+
+    /// The six neighbors on a hexagonal grid with pointy tops, in axial coordinates, laid
+    /// out as the keys around `s`: `d` east, `a` west, `e` north east, `q` north west, `c`
+    /// south east and `z` south west.
+    pub const HEX: Alphabet = Alphabet {
+        moves: Cow::Borrowed(&[
+            ('d', EAST),
+            ('a', WEST),
+            ('e', NORTH),
+            ('q', Point::new(-1, 1)),
+            ('c', Point::new(1, -1)),
+            ('z', SOUTH),
+        ]),
+    };
+
+    /// Maps `direction` to `step`, replacing its previous step if it had one.
+    pub fn with(mut self, direction: char, step: Point) -> Alphabet {
+        let moves = self.moves.to_mut();
+        moves.retain(|(c, _)| *c != direction);
+        moves.push((direction, step));
+        self
+    }
+
+    pub fn step(&self, direction: char) -> Result<Point, ErrorKind> {
+        self.moves
+            .iter()
+            .find(|(c, _)| *c == direction)
+            .map(|(_, step)| *step)
+            .ok_or(ErrorKind::UnexpectedChar(direction))
+    }
+}
+
+impl Default for Alphabet {
+    fn default() -> Self {
+        Alphabet::CARDINAL
+    }
+}
+
+impl FromIterator<(char, Point)> for Alphabet {
+    fn from_iter<I: IntoIterator<Item = (char, Point)>>(moves: I) -> Self {
+        let empty = Alphabet {
+            moves: Cow::Owned(Vec::new()),
+        };
+        moves
+            .into_iter()
+            .fold(empty, |alphabet, (c, step)| alphabet.with(c, step))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn walk(alphabet: &Alphabet, directions: &str) -> Result<Point, ErrorKind> {
+        directions
+            .chars()
+            .try_fold(Point::ORIGIN, |at, c| Ok(at + alphabet.step(c)?))
+    }
+
+    #[test]
+    fn test_builtin_alphabets() {
+        assert_eq!(walk(&Alphabet::CARDINAL, "^^>v<<"), Ok(Point::new(-1, 1)));
+        assert_eq!(
+            walk(&Alphabet::CARDINAL, "^7"),
+            Err(ErrorKind::UnexpectedChar('7'))
+        );
+        assert_eq!(walk(&Alphabet::EIGHT_WAY, "^7993"), Ok(Point::new(2, 3)));
+        // around a hexagon and back
+        assert_eq!(walk(&Alphabet::HEX, "eqazcd"), Ok(Point::ORIGIN));
+        assert_eq!(walk(&Alphabet::HEX, "eqq"), Ok(Point::new(-2, 3)));
+        assert_eq!(
+            walk(&Alphabet::HEX, "^"),
+            Err(ErrorKind::UnexpectedChar('^'))
+        );
+    }
+
+    #[test]
+    fn test_custom_alphabets() {
+        let letters: Alphabet = [('U', NORTH), ('D', SOUTH), ('R', EAST), ('L', WEST)]
+            .into_iter()
+            .collect();
+        assert_eq!(walk(&letters, "UURDL"), Ok(Point::new(0, 1)));
+        let knight = Alphabet::CARDINAL
+            .with('k', Point::new(1, 2))
+            .with('^', NORTH + NORTH);
+        assert_eq!(walk(&knight, "k^"), Ok(Point::new(1, 4)));
+        // remapping replaces the previous step
+        let backwards = Alphabet::CARDINAL.with('>', WEST);
+        assert_eq!(walk(&backwards, ">"), Ok(WEST));
+        assert_eq!(backwards.moves.iter().filter(|(c, _)| *c == '>').count(), 1);
+        assert_eq!(backwards.moves.len(), Alphabet::CARDINAL.moves.len());
+        assert_eq!(Alphabet::default(), Alphabet::CARDINAL);
+    }
+}
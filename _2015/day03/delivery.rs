@@ -0,0 +1,254 @@
+use _2015::ErrorKind;
+
+use crate::{Grid, HouseLocation, PresentRoute, TravelingSanta, alphabet::Alphabet};
+
+/// Decides which agent follows each direction.
+pub trait Schedule {
+    /// The agent following the direction at `turn`, below `agents`.
+    fn agent(&self, turn: usize, agents: usize) -> usize;
+}
+
+/// Agents follow one direction each in turn, as Santa and Robo-Santa do.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct RoundRobin;
+
+impl Schedule for RoundRobin {
+    fn agent(&self, turn: usize, agents: usize) -> usize {
+        turn % agents
+    }
+}
+
+/// Agents follow `size` directions in a row each in turn.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Blocks {
+    pub size: usize,
+}
+
+impl Schedule for Blocks {
+    fn agent(&self, turn: usize, agents: usize) -> usize {
+        (turn / self.size.max(1)) % agents
+    }
+}
+
+/// Agents take turns, agent `i` following `weights[i]` directions in a row.
+///
+/// Agents without a weight follow one direction per round, a weight of 0 skips the agent.
+/// If every weight is 0, agents take turns as with `RoundRobin`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Weighted {
+    pub weights: Vec<usize>,
+}
+
+impl Weighted {
+    fn weight(&self, agent: usize) -> usize {
+        self.weights.get(agent).copied().unwrap_or(1)
+    }
+}
+
+impl Schedule for Weighted {
+    fn agent(&self, turn: usize, agents: usize) -> usize {
+        let round: usize = (0..agents).map(|agent| self.weight(agent)).sum();
+        if round == 0 {
+            return RoundRobin.agent(turn, agents);
+        }
+        let mut turn = turn % round;
+        (0..agents)
+            .find(|&agent| match turn.checked_sub(self.weight(agent)) {
+                Some(rest) => {
+                    turn = rest;
+                    false
+                }
+                None => true,
+            })
+            .expect("the turn falls within the round")
+    }
+}
+
+/// An agent once every direction is followed.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct AgentStats {
+    pub location: HouseLocation,
+    /// the number of directions followed
+    pub moves: usize,
+    /// the presents delivered by this agent, the one at the start included
+    pub houses: Grid,
+}
+
+/// Presents delivered by any number of agents sharing the directions.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Delivery {
+    /// never empty, `run_with` needs at least one agent
+    agents: Vec<AgentStats>,
+}
+
+impl Delivery {
+    /// Every agent delivers a present where it starts, then follows the directions
+    /// `schedule` gives it.
+    pub fn run(
+        directions: &str,
+        agents: usize,
+        schedule: impl Schedule,
+    ) -> Result<Delivery, ErrorKind> {
+        Delivery::run_with(directions, agents, schedule, &Alphabet::CARDINAL)
+    }
+
+    /// Runs a delivery with directions written in `alphabet`.
+    pub fn run_with(
+        directions: &str,
+        agents: usize,
+        schedule: impl Schedule,
+        alphabet: &Alphabet,
+    ) -> Result<Delivery, ErrorKind> {
+        let route = PresentRoute::scheduled(directions.chars(), agents, schedule)?;
+        let start = HouseLocation::ORIGIN;
+        let mut santas: Vec<TravelingSanta> = (0..agents)
+            .map(|_| TravelingSanta {
+                current_location: start,
+            })
+            .collect();
+        let mut stats: Vec<AgentStats> = (0..agents)
+            .map(|_| AgentStats {
+                location: start,
+                moves: 0,
+                houses: Grid::new(),
+            })
+            .collect();
+        for agent in &mut stats {
+            agent.houses.visit(start);
+        }
+        for (agent, direction) in route {
+            let santa = &mut santas[agent];
+            santa.deliver_with(direction, alphabet)?;
+            stats[agent].moves += 1;
+            stats[agent].houses.visit(santa.current_location);
+        }
+        for (agent, santa) in stats.iter_mut().zip(santas) {
+            agent.location = santa.current_location;
+        }
+        Ok(Delivery { agents: stats })
+    }
+
+    pub fn agents(&self) -> &[AgentStats] {
+        &self.agents
+    }
+
+    /// The presents each house got from any agent.
+    pub fn union(&self) -> Grid {
+        let mut union = Grid::new();
+        for agent in &self.agents {
+            for (house, visits) in &agent.houses.visits {
+                *union.visits.entry(*house).or_default() += visits;
+            }
+        }
+        union
+    }
+
+    /// The houses every agent delivered a present to, by `x` then `y`.
+    pub fn intersection(&self) -> Vec<HouseLocation> {
+        let (first, others) = self
+            .agents
+            .split_first()
+            .expect("there is at least one agent");
+        let mut houses: Vec<HouseLocation> = first
+            .houses
+            .visits
+            .keys()
+            .filter(|house| others.iter().all(|agent| agent.houses.visits(**house) > 0))
+            .copied()
+            .collect();
+        houses.sort_by_key(|house| (house.x, house.y));
+        houses
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn turns(schedule: impl Schedule, agents: usize, count: usize) -> Vec<usize> {
+        (0..count)
+            .map(|turn| schedule.agent(turn, agents))
+            .collect()
+    }
+
+    #[test]
+    fn test_schedules() {
+        assert_eq!(turns(RoundRobin, 3, 7), [0, 1, 2, 0, 1, 2, 0]);
+        assert_eq!(turns(Blocks { size: 2 }, 2, 7), [0, 0, 1, 1, 0, 0, 1]);
+        assert_eq!(turns(Blocks { size: 0 }, 2, 3), [0, 1, 0]);
+        let weighted = Weighted {
+            weights: vec![3, 0, 1],
+        };
+        assert_eq!(turns(weighted.clone(), 3, 9), [0, 0, 0, 2, 0, 0, 0, 2, 0]);
+        // the fourth agent has no weight
+        assert_eq!(turns(weighted, 4, 6), [0, 0, 0, 2, 3, 0]);
+        let idle = Weighted {
+            weights: vec![0, 0],
+        };
+        assert_eq!(turns(idle, 2, 3), [0, 1, 0]);
+    }
+
+    #[test]
+    fn test_santa_and_robot() {
+        let delivery = Delivery::run("^v^v^v^v^v", 2, RoundRobin).unwrap();
+        assert_eq!(delivery.union().len(), 11);
+        assert_eq!(delivery.union().visits(HouseLocation::ORIGIN), 2);
+        let [santa, robot] = delivery.agents() else {
+            panic!("two agents");
+        };
+        assert_eq!((santa.moves, robot.moves), (5, 5));
+        assert_eq!(santa.location, HouseLocation::new(0, 5));
+        assert_eq!(robot.location, HouseLocation::new(0, -5));
+        assert_eq!(santa.houses.len(), 6);
+        assert_eq!(delivery.intersection(), [HouseLocation::ORIGIN]);
+        // both come back to the start
+        let back = Delivery::run("^>v<", 2, RoundRobin).unwrap();
+        assert_eq!(back.union().len(), 3);
+        assert_eq!(back.union().visits(HouseLocation::ORIGIN), 4);
+    }
+
+    #[test]
+    fn test_many_agents() {
+        let delivery = Delivery::run("^>v<^>v<", 4, Blocks { size: 2 }).unwrap();
+        let moves: Vec<usize> = delivery.agents().iter().map(|a| a.moves).collect();
+        assert_eq!(moves, [2, 2, 2, 2]);
+        // the first agent goes up and right, the second down and left
+        assert_eq!(delivery.agents()[1].location, HouseLocation::new(-1, -1));
+        assert_eq!(delivery.union().len(), 5);
+        assert_eq!(delivery.intersection(), [HouseLocation::ORIGIN]);
+
+        let alone = Delivery::run("^>v<", 1, RoundRobin).unwrap();
+        assert_eq!(alone.union().visits(HouseLocation::ORIGIN), 2);
+        assert_eq!(alone.intersection().len(), 4);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(Delivery::run("^", 0, RoundRobin).is_err());
+        assert_eq!(
+            Delivery::run("^x", 2, RoundRobin),
+            Err(ErrorKind::UnexpectedChar('x'))
+        );
+    }
+
+    #[test]
+    fn test_alphabets() {
+        let diagonal = Delivery::run_with("9937", 2, RoundRobin, &Alphabet::EIGHT_WAY).unwrap();
+        assert_eq!(diagonal.agents()[0].location, HouseLocation::new(2, 0));
+        assert_eq!(diagonal.agents()[1].location, HouseLocation::new(0, 2));
+        assert_eq!(diagonal.union().len(), 4);
+        assert_eq!(diagonal.intersection()[1], HouseLocation::new(1, 1));
+        let hex = Delivery::run_with("eqazcd", 1, RoundRobin, &Alphabet::HEX).unwrap();
+        assert_eq!(hex.union().len(), 6);
+        assert_eq!(hex.union().visits(HouseLocation::ORIGIN), 2);
+        let letters = Alphabet::CARDINAL.with('U', HouseLocation::new(0, 1));
+        assert_eq!(
+            Delivery::run_with("U^v", 1, RoundRobin, &letters).unwrap(),
+            Delivery::run("^^v", 1, RoundRobin).unwrap()
+        );
+        assert_eq!(
+            Delivery::run_with("^", 1, RoundRobin, &Alphabet::HEX),
+            Err(ErrorKind::UnexpectedChar('^'))
+        );
+    }
+}
@@ -1,13 +1,79 @@
-use std::{collections::HashSet, str::Chars};
+pub mod alphabet;
+pub mod delivery;
+pub mod render;
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
-pub struct HouseLocation {
-    pub x: i32,
-    pub y: i32,
+use _2015::{ErrorKind, Point, parse};
+use alphabet::Alphabet;
+use aoc_core::{Answer, Error, Solver};
+use delivery::{Delivery, RoundRobin, Schedule};
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashMap},
+    str::Chars,
+};
+
+pub type HouseLocation = Point;
+
+/// The number of presents each house got.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Grid {
+    visits: HashMap<HouseLocation, u32>,
 }
 
-pub type Grid = HashSet<HouseLocation>;
+impl Grid {
+    pub fn new() -> Grid {
+        Grid::default()
+    }
+
+    /// Delivers one more present to `house`.
+    pub fn visit(&mut self, house: HouseLocation) {
+        *self.visits.entry(house).or_default() += 1;
+    }
+
+    pub fn visits(&self, house: HouseLocation) -> u32 {
+        self.visits.get(&house).copied().unwrap_or_default()
+    }
+
+    /// The number of houses with at least one present.
+    pub fn len(&self) -> usize {
+        self.visits.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.visits.is_empty()
+    }
+
+    /// The house with the most presents and how many, the lowest `x` then `y` on ties.
+    pub fn most_visited(&self) -> Option<(HouseLocation, u32)> {
+        self.visits
+            .iter()
+            .max_by_key(|(house, visits)| (**visits, Reverse((house.x, house.y))))
+            .map(|(house, visits)| (*house, *visits))
+    }
+
+    /// The number of houses for each number of presents.
+    pub fn distribution(&self) -> BTreeMap<u32, usize> {
+        let mut houses = BTreeMap::new();
+        for visits in self.visits.values() {
+            *houses.entry(*visits).or_default() += 1;
+        }
+        houses
+    }
 
+    /// The houses that got exactly `visits` presents, by `x` then `y`.
+    pub fn visited_exactly(&self, visits: u32) -> Vec<HouseLocation> {
+        let mut houses: Vec<HouseLocation> = self
+            .visits
+            .iter()
+            .filter(|(_, v)| **v == visits)
+            .map(|(house, _)| *house)
+            .collect();
+        houses.sort_by_key(|house| (house.x, house.y));
+        houses
+    }
+}
+
+/// A move of the puzzle's directions, `Alphabet` has diagonal, hex and custom moves.
 pub enum SantaDestinationTravel {
     North { from: HouseLocation },
     South { from: HouseLocation },
@@ -16,26 +82,37 @@ pub enum SantaDestinationTravel {
 }
 
 impl SantaDestinationTravel {
-    pub fn travel(&self) -> HouseLocation {
-        match &self {
-            SantaDestinationTravel::North { from } => HouseLocation {
-                x: from.x,
-                y: from.y + 1,
-            },
-            SantaDestinationTravel::South { from } => HouseLocation {
-                x: from.x,
-                y: from.y - 1,
-            },
-            SantaDestinationTravel::West { from } => HouseLocation {
-                x: from.x - 1,
-                y: from.y,
-            },
-            SantaDestinationTravel::East { from } => HouseLocation {
-                x: from.x + 1,
-                y: from.y,
-            },
+    /// The move `direction` stands for, starting at `from`.
+    pub fn new(direction: char, from: HouseLocation) -> Result<Self, ErrorKind> {
+        [
+            SantaDestinationTravel::North { from },
+            SantaDestinationTravel::South { from },
+            SantaDestinationTravel::West { from },
+            SantaDestinationTravel::East { from },
+        ]
+        .into_iter()
+        .find(|travel| travel.direction() == direction)
+        .ok_or(ErrorKind::UnexpectedChar(direction))
+    }
+
+    /// The character `Alphabet::CARDINAL` writes this move with.
+    pub fn direction(&self) -> char {
+        match self {
+            SantaDestinationTravel::North { .. } => '^',
+            SantaDestinationTravel::South { .. } => 'v',
+            SantaDestinationTravel::West { .. } => '<',
+            SantaDestinationTravel::East { .. } => '>',
         }
     }
+
+    pub fn travel(&self) -> HouseLocation {
+        let (SantaDestinationTravel::North { from }
+        | SantaDestinationTravel::South { from }
+        | SantaDestinationTravel::West { from }
+        | SantaDestinationTravel::East { from }) = self;
+        let step = Alphabet::CARDINAL.step(self.direction());
+        *from + step.expect("every move is in the cardinal alphabet")
+    }
 }
 
 pub struct TravelingSanta {
@@ -43,35 +120,27 @@ pub struct TravelingSanta {
 }
 
 impl TravelingSanta {
-    pub fn deliver_present(&mut self, direction: char) -> Result<(), String> {
-        // north (^), south (v), east (>), or west (<)
-        let destination: SantaDestinationTravel = match direction {
-            '^' => SantaDestinationTravel::North {
-                from: self.current_location,
-            },
-            'v' => SantaDestinationTravel::South {
-                from: self.current_location,
-            },
-            '>' => SantaDestinationTravel::East {
-                from: self.current_location,
-            },
-            '<' => SantaDestinationTravel::West {
-                from: self.current_location,
-            },
-            unknown => return Err(format!("unknown destination: {}", unknown)),
-        };
+    /// Moves north (^), south (v), east (>), or west (<).
+    pub fn deliver_present(&mut self, direction: char) -> Result<(), ErrorKind> {
+        let destination = SantaDestinationTravel::new(direction, self.current_location)?;
         self.current_location = destination.travel();
         Ok(())
     }
+
+    /// Moves by the step `alphabet` maps `direction` to.
+    pub fn deliver_with(&mut self, direction: char, alphabet: &Alphabet) -> Result<(), ErrorKind> {
+        self.current_location += alphabet.step(direction)?;
+        Ok(())
+    }
 }
 
 fn deliver_and_mark(
     santa: &mut TravelingSanta,
     houses_with_presents: &mut Grid,
     char: char,
-) -> Result<(), String> {
+) -> Result<(), ErrorKind> {
     santa.deliver_present(char)?;
-    houses_with_presents.insert(santa.current_location);
+    houses_with_presents.visit(santa.current_location);
     Ok(())
 }
 
@@ -79,53 +148,130 @@ pub fn deliver_presents_single_santa(
     santa: &mut TravelingSanta,
     houses_with_presents: &mut Grid,
     input_file_content: String,
-) -> Result<(), String> {
-    Ok(for char in input_file_content.chars() {
+) -> Result<(), ErrorKind> {
+    for char in input_file_content.chars() {
         deliver_and_mark(santa, houses_with_presents, char)?;
-    })
+    }
+    Ok(())
 }
 
-pub struct PresentRoute<'a> {
+/// Directions paired with the agent following them.
+pub struct PresentRoute<'a, S = RoundRobin> {
     input: Chars<'a>,
     turn: usize,
+    agents: usize,
+    schedule: S,
 }
 
 impl<'a> PresentRoute<'a> {
+    /// Santa and Robo-Santa taking turns.
     pub fn new(input: Chars<'a>) -> Self {
-        Self { input, turn: 0 }
+        Self {
+            input,
+            turn: 0,
+            agents: 2,
+            schedule: RoundRobin,
+        }
+    }
+}
+
+impl<'a, S: Schedule> PresentRoute<'a, S> {
+    /// Fails without `agents` to follow the directions.
+    pub fn scheduled(input: Chars<'a>, agents: usize, schedule: S) -> Result<Self, ErrorKind> {
+        if agents == 0 {
+            return Err(ErrorKind::Invalid(
+                "at least one agent is needed".to_string(),
+            ));
+        }
+        Ok(Self {
+            input,
+            turn: 0,
+            agents,
+            schedule,
+        })
     }
 }
 
-impl<'a> Iterator for PresentRoute<'a> {
+impl<'a, S: Schedule> Iterator for PresentRoute<'a, S> {
     type Item = (usize, char);
 
     fn next(&mut self) -> Option<Self::Item> {
         self.input.next().map(|c| {
-            let agent_id = self.turn % 2;
+            let agent_id = self.schedule.agent(self.turn, self.agents);
             self.turn += 1;
             (agent_id, c)
         })
     }
 }
 
+/// Santa and Robo-Santa taking turns, the houses they reach are added to
+/// `houses_with_presents` from where each of them stands.
 pub fn deliver_presents_santa_and_robot(
     santa: &mut TravelingSanta,
     robot_santa: &mut TravelingSanta,
     houses_with_presents: &mut Grid,
     input_file_content: String,
-) -> Result<(), String> {
-    let present_route = PresentRoute::new(input_file_content.chars());
-    for (agent_id, direction) in present_route {
-        let which_santa = if agent_id % 2 == 0 {
-            &mut *santa
-        } else {
-            &mut *robot_santa
-        };
-        deliver_and_mark(which_santa, houses_with_presents, direction)?
+) -> Result<(), ErrorKind> {
+    let delivery = Delivery::run(&input_file_content, 2, RoundRobin)?;
+    for (santa, agent) in [santa, robot_santa].into_iter().zip(delivery.agents()) {
+        let start = santa.current_location;
+        for (&house, &visits) in &agent.houses.visits {
+            // the delivery leaves a present where each agent starts, not counted here
+            let visits = if house == HouseLocation::ORIGIN {
+                visits - 1
+            } else {
+                visits
+            };
+            if visits > 0 {
+                *houses_with_presents
+                    .visits
+                    .entry(start + house)
+                    .or_default() += visits;
+            }
+        }
+        santa.current_location = start + agent.location;
     }
     Ok(())
 }
 
+/// The directions of the input, and where Santa alone, then Santa and Robo-Santa, deliver
+/// presents following them.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Deliveries<'a> {
+    pub directions: &'a str,
+    pub santa: Delivery,
+    pub santa_and_robot: Delivery,
+}
+
+pub struct Day03;
+
+impl Solver for Day03 {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 3;
+
+    type Input<'a> = Deliveries<'a>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        let directions = input.trim();
+        parse::chars(directions, |c| Alphabet::CARDINAL.step(c).map(drop))?;
+        Ok(Deliveries {
+            directions,
+            santa: Delivery::run(directions, 1, RoundRobin)?,
+            santa_and_robot: Delivery::run(directions, 2, RoundRobin)?,
+        })
+    }
+
+    fn part1(deliveries: &Self::Input<'_>) -> Answer {
+        deliveries.santa.union().len().into()
+    }
+
+    fn part2(deliveries: &Self::Input<'_>) -> Answer {
+        deliveries.santa_and_robot.union().len().into()
+    }
+}
+
+aoc_core::examples!(Day03: one_move, square, back_and_forth, split_up);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -135,8 +281,8 @@ mod tests {
         let mut santa = TravelingSanta {
             current_location: HouseLocation { x: 0, y: 0 },
         };
-        let mut houses_with_presents: Grid = std::collections::HashSet::new();
-        houses_with_presents.insert(santa.current_location);
+        let mut houses_with_presents = Grid::new();
+        houses_with_presents.visit(santa.current_location);
         let directions = ">".to_string();
         let _ = deliver_presents_single_santa(&mut santa, &mut houses_with_presents, directions);
 
@@ -145,15 +291,15 @@ mod tests {
 
     #[test]
     fn test_deliver_presents_santa_and_robot() {
-        let start = HouseLocation { x: 0, y: 0 };
+        let start = HouseLocation::ORIGIN;
         let mut santa = TravelingSanta {
             current_location: start,
         };
         let mut robot_santa = TravelingSanta {
             current_location: start,
         };
-        let mut houses_with_presents: Grid = std::collections::HashSet::new();
-        houses_with_presents.insert(start);
+        let mut houses_with_presents = Grid::new();
+        houses_with_presents.visit(start);
         let map = String::from("^>v<");
         let _ = deliver_presents_santa_and_robot(
             &mut santa,
@@ -163,4 +309,105 @@ mod tests {
         );
         assert_eq!(houses_with_presents.len(), 3)
     }
+
+    #[test]
+    fn test_santa_and_robot_match_delivery() {
+        let directions = "^v^v^v^v^v>>><<^";
+        let mut santa = TravelingSanta {
+            current_location: HouseLocation::ORIGIN,
+        };
+        let mut robot_santa = TravelingSanta {
+            current_location: HouseLocation::ORIGIN,
+        };
+        let mut grid = Grid::new();
+        grid.visit(HouseLocation::ORIGIN);
+        deliver_presents_santa_and_robot(
+            &mut santa,
+            &mut robot_santa,
+            &mut grid,
+            directions.into(),
+        )
+        .unwrap();
+        let delivery = Delivery::run(directions, 2, RoundRobin).unwrap();
+        assert_eq!(grid.len(), delivery.union().len());
+        assert_eq!(
+            grid.visits(HouseLocation::ORIGIN) + 1,
+            delivery.union().visits(HouseLocation::ORIGIN)
+        );
+        assert_eq!(santa.current_location, delivery.agents()[0].location);
+        assert_eq!(robot_santa.current_location, delivery.agents()[1].location);
+    }
+
+    #[test]
+    fn test_destination_travel() {
+        let from = HouseLocation::new(2, -1);
+        for direction in ['^', 'v', '>', '<'] {
+            let travel = SantaDestinationTravel::new(direction, from).unwrap();
+            assert_eq!(travel.direction(), direction);
+            let step = Alphabet::CARDINAL.step(direction).unwrap();
+            assert_eq!(travel.travel(), from + step);
+        }
+        assert!(matches!(
+            SantaDestinationTravel::new('^', from),
+            Ok(SantaDestinationTravel::North { .. })
+        ));
+        assert!(SantaDestinationTravel::new('7', from).is_err());
+        let mut santa = TravelingSanta {
+            current_location: from,
+        };
+        santa.deliver_present('<').unwrap();
+        assert_eq!(santa.current_location, HouseLocation::new(1, -1));
+        assert_eq!(
+            santa.deliver_present('9'),
+            Err(ErrorKind::UnexpectedChar('9'))
+        );
+    }
+
+    #[test]
+    fn test_route_without_agents() {
+        assert!(PresentRoute::scheduled("^".chars(), 0, RoundRobin).is_err());
+        assert_eq!(PresentRoute::new("^>".chars()).last(), Some((1, '>')));
+    }
+
+    #[test]
+    fn test_visit_counts() {
+        let mut santa = TravelingSanta {
+            current_location: HouseLocation::ORIGIN,
+        };
+        let mut grid = Grid::new();
+        grid.visit(HouseLocation::ORIGIN);
+        deliver_presents_single_santa(&mut santa, &mut grid, "^v^v^v>".to_string()).unwrap();
+        assert_eq!(grid.len(), 3);
+        assert_eq!(grid.visits(HouseLocation::ORIGIN), 4);
+        assert_eq!(grid.visits(HouseLocation::new(5, 5)), 0);
+        assert_eq!(grid.most_visited(), Some((HouseLocation::ORIGIN, 4)));
+        assert_eq!(
+            grid.distribution(),
+            BTreeMap::from([(1, 1), (3, 1), (4, 1)])
+        );
+        assert_eq!(grid.visited_exactly(1), [HouseLocation::new(1, 0)]);
+        assert!(grid.visited_exactly(2).is_empty());
+    }
+
+    #[test]
+    fn test_most_visited_ties() {
+        let mut grid = Grid::new();
+        assert_eq!(grid.most_visited(), None);
+        for house in [(2, 0), (-1, 3), (-1, 1), (0, 0)] {
+            grid.visit(HouseLocation::new(house.0, house.1));
+        }
+        assert_eq!(grid.most_visited(), Some((HouseLocation::new(-1, 1), 1)));
+        assert_eq!(
+            grid.visited_exactly(1),
+            [(-1, 1), (-1, 3), (0, 0), (2, 0)].map(|(x, y)| HouseLocation::new(x, y))
+        );
+    }
+
+    #[test]
+    fn test_solver() {
+        let directions = Day03::parse("^v^v^v^v^v\n").unwrap();
+        assert_eq!(Day03::part1(&directions), Answer::from(2usize));
+        assert_eq!(Day03::part2(&directions), Answer::from(11usize));
+        assert!(Day03::parse("^x").is_err());
+    }
 }
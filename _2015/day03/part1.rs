@@ -1,22 +1,10 @@
-use std::{collections::HashSet, error::Error};
-
-use _2015_day03::Grid;
-use _2015_day03::HouseLocation;
-use _2015_day03::TravelingSanta;
-use _2015_day03::deliver_presents_single_santa;
-
-fn main() -> Result<(), Box<dyn Error>> {
-    let start = HouseLocation { x: 0, y: 0 };
-    let mut santa = TravelingSanta {
-        current_location: start,
-    };
-    let mut houses_with_presents: Grid = HashSet::new();
-    houses_with_presents.insert(start);
-
-    let input_file_content = std::fs::read_to_string("_2015/day03/input.txt")?;
-    deliver_presents_single_santa(&mut santa, &mut houses_with_presents, input_file_content)?;
-
-    println!("{}", houses_with_presents.len());
-
+use _2015_day03::Day03;
+use aoc_core::{Solver, input};
+
+fn main() -> Result<(), aoc_core::Error> {
+    let input_file_content =
+        input::read(Day03::YEAR, Day03::DAY, std::env::args().nth(1).as_deref())?;
+    let directions = Day03::parse(&input_file_content)?;
+    println!("{}", Day03::part1(&directions));
     Ok(())
 }
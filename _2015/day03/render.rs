@@ -0,0 +1,254 @@
+//! Heatmaps of the houses presents were delivered to, as PPM or SVG images.
+//!
+//! Each house is painted in the color of the agent that left it the most presents, brighter
+//! the more presents it got in total. North is up.
+
+use std::io::{self, Write};
+
+use crate::{HouseLocation, delivery::Delivery};
+
+/// Agent colors, reused when there are more agents.
+pub const PALETTE: [[u8; 3]; 6] = [
+    [230, 57, 70],
+    [69, 123, 157],
+    [42, 157, 143],
+    [233, 196, 106],
+    [168, 218, 220],
+    [244, 162, 97],
+];
+
+/// The color of houses without presents.
+pub const BACKGROUND: [u8; 3] = [16, 16, 16];
+
+/// Share of its agent's color a house with a single present gets.
+const DIMMEST: f64 = 0.35;
+
+/// The smallest rectangle holding a set of houses, edges included.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Bounds {
+    pub min: HouseLocation,
+    pub max: HouseLocation,
+}
+
+impl Bounds {
+    pub fn of(houses: impl IntoIterator<Item = HouseLocation>) -> Option<Bounds> {
+        houses.into_iter().fold(None, |bounds, house| {
+            let Bounds { min, max } = bounds.unwrap_or(Bounds {
+                min: house,
+                max: house,
+            });
+            Some(Bounds {
+                min: HouseLocation::new(min.x.min(house.x), min.y.min(house.y)),
+                max: HouseLocation::new(max.x.max(house.x), max.y.max(house.y)),
+            })
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.x - self.min.x) as usize + 1
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.y - self.min.y) as usize + 1
+    }
+
+    /// Column and row of `house` in an image of the bounds, north up.
+    fn cell(&self, house: HouseLocation) -> (usize, usize) {
+        (
+            (house.x - self.min.x) as usize,
+            (self.max.y - house.y) as usize,
+        )
+    }
+}
+
+/// A visited house in an image.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct Cell {
+    house: HouseLocation,
+    presents: u32,
+    color: [u8; 3],
+}
+
+/// The colors of the houses of a delivery.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Heatmap {
+    pub bounds: Bounds,
+    /// row by row from the north west corner, `None` for houses without presents
+    cells: Vec<Option<Cell>>,
+}
+
+fn blend(color: [u8; 3], share: f64) -> [u8; 3] {
+    let mut blended = BACKGROUND;
+    for (channel, target) in blended.iter_mut().zip(color) {
+        *channel = (*channel as f64 + (target as f64 - *channel as f64) * share).round() as u8;
+    }
+    blended
+}
+
+impl Heatmap {
+    pub fn new(delivery: &Delivery) -> Heatmap {
+        let union = delivery.union();
+        // every agent delivers a present where it starts, so this is only a fallback
+        let start = Bounds {
+            min: HouseLocation::ORIGIN,
+            max: HouseLocation::ORIGIN,
+        };
+        let bounds = Bounds::of(union.visits.keys().copied()).unwrap_or(start);
+        let most = union.most_visited().map_or(1, |(_, presents)| presents);
+        let mut cells = vec![None; bounds.width() * bounds.height()];
+        for (&house, &presents) in &union.visits {
+            // the agent that left the most presents, the first one on ties
+            let agent = delivery
+                .agents()
+                .iter()
+                .enumerate()
+                .rev()
+                .max_by_key(|(_, agent)| agent.houses.visits(house))
+                .map_or(0, |(agent, _)| agent);
+            // on a log scale, or the few most visited houses would dim every other one
+            let share = match most {
+                1 => 1.0,
+                _ => DIMMEST + (1.0 - DIMMEST) * (presents as f64).ln() / (most as f64).ln(),
+            };
+            let (column, row) = bounds.cell(house);
+            cells[row * bounds.width() + column] = Some(Cell {
+                house,
+                presents,
+                color: blend(PALETTE[agent % PALETTE.len()], share),
+            });
+        }
+        Heatmap { bounds, cells }
+    }
+
+    /// The color of `house`, `None` outside of the bounds.
+    pub fn color(&self, house: HouseLocation) -> Option<[u8; 3]> {
+        let inside = (self.bounds.min.x..=self.bounds.max.x).contains(&house.x)
+            && (self.bounds.min.y..=self.bounds.max.y).contains(&house.y);
+        if !inside {
+            return None;
+        }
+        let (column, row) = self.bounds.cell(house);
+        let cell = self.cells[row * self.bounds.width() + column];
+        Some(cell.map_or(BACKGROUND, |cell| cell.color))
+    }
+
+    /// Writes a binary PPM image, each house `scale` pixels wide.
+    pub fn write_ppm(&self, mut out: impl Write, scale: usize) -> io::Result<()> {
+        let (width, height) = (self.bounds.width(), self.bounds.height());
+        writeln!(out, "P6\n{} {}\n255", width * scale, height * scale)?;
+        let mut line = Vec::with_capacity(width * scale * 3);
+        for row in self.cells.chunks(width) {
+            line.clear();
+            for cell in row {
+                let color = cell.map_or(BACKGROUND, |cell| cell.color);
+                for _ in 0..scale {
+                    line.extend(color);
+                }
+            }
+            for _ in 0..scale {
+                out.write_all(&line)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes an SVG image, each house `scale` pixels wide and titled with its presents.
+    pub fn write_svg(&self, mut out: impl Write, scale: usize) -> io::Result<()> {
+        let (width, height) = (self.bounds.width(), self.bounds.height());
+        let hex = |[r, g, b]: [u8; 3]| format!("#{r:02x}{g:02x}{b:02x}");
+        writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {width} {height}" shape-rendering="crispEdges">"#,
+            width * scale,
+            height * scale,
+        )?;
+        writeln!(
+            out,
+            r#"<rect width="{width}" height="{height}" fill="{}"/>"#,
+            hex(BACKGROUND)
+        )?;
+        for (i, cell) in self.cells.iter().enumerate() {
+            let Some(Cell {
+                house,
+                presents,
+                color,
+            }) = cell
+            else {
+                continue;
+            };
+            writeln!(
+                out,
+                r#"<rect x="{}" y="{}" width="1" height="1" fill="{}"><title>{},{}: {presents}</title></rect>"#,
+                i % width,
+                i / width,
+                hex(*color),
+                house.x,
+                house.y,
+            )?;
+        }
+        writeln!(out, "</svg>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::delivery::RoundRobin;
+
+    fn heatmap(directions: &str, agents: usize) -> Heatmap {
+        Heatmap::new(&Delivery::run(directions, agents, RoundRobin).unwrap())
+    }
+
+    #[test]
+    fn test_bounds() {
+        let houses = [(2, -1), (-3, 4), (0, 0)].map(|(x, y)| HouseLocation::new(x, y));
+        let bounds = Bounds::of(houses).unwrap();
+        assert_eq!(bounds.min, HouseLocation::new(-3, -1));
+        assert_eq!(bounds.max, HouseLocation::new(2, 4));
+        assert_eq!((bounds.width(), bounds.height()), (6, 6));
+        assert_eq!(bounds.cell(HouseLocation::new(-3, 4)), (0, 0));
+        assert_eq!(Bounds::of([]), None);
+    }
+
+    #[test]
+    fn test_colors() {
+        // Santa goes up twice, Robo-Santa goes right and comes back
+        let heatmap = heatmap("^>^<", 2);
+        assert_eq!(heatmap.bounds.width(), 2);
+        assert_eq!(heatmap.bounds.height(), 3);
+        let [santa, robot] = [PALETTE[0], PALETTE[1]];
+        // the start got the most presents, three, one from Santa and two from Robo-Santa
+        assert_eq!(heatmap.color(HouseLocation::ORIGIN), Some(robot));
+        assert_eq!(
+            heatmap.color(HouseLocation::new(0, 2)),
+            Some(blend(santa, DIMMEST))
+        );
+        assert_eq!(heatmap.color(HouseLocation::new(1, 2)), Some(BACKGROUND));
+        assert_eq!(heatmap.color(HouseLocation::new(2, 0)), None);
+    }
+
+    #[test]
+    fn test_ppm() {
+        let mut ppm = Vec::new();
+        heatmap(">", 1).write_ppm(&mut ppm, 2).unwrap();
+        let header = b"P6\n4 2\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        let pixels = &ppm[header.len()..];
+        assert_eq!(pixels.len(), 4 * 2 * 3);
+        // both houses got one present, the brightest there are
+        assert!(pixels.chunks(3).all(|pixel| pixel == PALETTE[0]));
+    }
+
+    #[test]
+    fn test_svg() {
+        let mut svg = Vec::new();
+        heatmap("^>v", 2).write_svg(&mut svg, 10).unwrap();
+        let svg = String::from_utf8(svg).unwrap();
+        assert!(
+            svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="20""#)
+        );
+        assert_eq!(svg.matches("<title>").count(), 3);
+        assert!(svg.contains(r##"<rect x="0" y="1" width="1" height="1" fill="#e63946"><title>0,0: 3</title></rect>"##));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+}
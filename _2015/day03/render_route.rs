@@ -0,0 +1,46 @@
+//! Draws the houses presents were delivered to, as a PPM or SVG image depending on the
+//! extension of the output file:
+//!
+//! ```sh
+//! cargo run --bin _2015_day03_render -- route.svg 2 [input]
+//! ```
+
+use std::{fs::File, io::BufWriter, path::Path};
+
+use _2015_day03::{
+    Day03,
+    delivery::{Delivery, RoundRobin},
+    render::Heatmap,
+};
+use aoc_core::{Solver, input};
+
+const USAGE: &str = "usage: _2015_day03_render <output.ppm|output.svg> [<agents>] [<input>]";
+
+/// Pixels per house.
+const SCALE: usize = 4;
+
+fn main() -> Result<(), aoc_core::Error> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (output, agents, input) = match &args[..] {
+        [output] => (output, 2, None),
+        [output, agents] => (output, agents.parse()?, None),
+        [output, agents, input] => (output, agents.parse()?, Some(input.as_str())),
+        _ => return Err(USAGE.into()),
+    };
+    let input_file_content = input::read(Day03::YEAR, Day03::DAY, input)?;
+    let directions = Day03::parse(&input_file_content)?.directions;
+    let heatmap = Heatmap::new(&Delivery::run(directions, agents, RoundRobin)?);
+
+    let path = Path::new(output);
+    let out = BufWriter::new(
+        File::create(path).map_err(|e| format!("failed to create {}: {e}", path.display()))?,
+    );
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("ppm") => heatmap.write_ppm(out, SCALE)?,
+        Some("svg") => heatmap.write_svg(out, SCALE)?,
+        _ => return Err(USAGE.into()),
+    }
+    let (width, height) = (heatmap.bounds.width(), heatmap.bounds.height());
+    println!("{width}x{height} houses drawn to {}", path.display());
+    Ok(())
+}
@@ -1,8 +1,8 @@
+use aoc_core::{Answer, Error, Solver};
 use md5::compute;
+use rayon::prelude::*;
 use std::ops::RangeFrom;
 
-pub const KEY: &str = "ckczppom";
-
 pub struct Md5HexIter {
     key: String,
     range_from: RangeFrom<u32>,
@@ -32,5 +32,61 @@ impl Iterator for Md5HexIter {
     }
 }
 
-#[cfg(test)]
-mod tests {}
+pub fn lowest_number_with_five_leading_zeros(key: &str) -> u32 {
+    let mut md5_iter = Md5HexIter::new(key.to_string(), 1..);
+    for md5 in md5_iter.by_ref() {
+        /*The third byte must be less than `0x10` (i.e., its high nibble is 0), which ensures the 5th hex digit is `0` while the 6th can be anything.*/
+        if md5[0] == 0 && md5[1] == 0 && md5[2] < 0x10 {
+            break;
+        }
+    }
+    md5_iter.current
+}
+
+pub fn lowest_number_with_six_leading_zeros(key: &str) -> u32 {
+    let md5_has_six_leading_zeros = |n: &u32| -> bool {
+        let md5: [u8; 16] = compute(format!("{}{}", key, n)).0;
+        md5[0] == 0 && md5[1] == 0 && md5[2] == 0
+    };
+
+    const SLICE_SIZE: u32 = 500_000;
+    let mut start: u32 = 0;
+    let mut end: u32 = start + SLICE_SIZE;
+    loop {
+        if let Some(result) = (start..end)
+            .into_par_iter()
+            .find_first(md5_has_six_leading_zeros)
+        {
+            return result;
+        } else {
+            start = end;
+            end += SLICE_SIZE;
+        }
+    }
+}
+
+pub struct Day04;
+
+impl Solver for Day04 {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 4;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        match input.trim() {
+            "" => Err("expected a secret key".into()),
+            key => Ok(key),
+        }
+    }
+
+    fn part1(key: &Self::Input<'_>) -> Answer {
+        lowest_number_with_five_leading_zeros(key).into()
+    }
+
+    fn part2(key: &Self::Input<'_>) -> Answer {
+        lowest_number_with_six_leading_zeros(key).into()
+    }
+}
+
+aoc_core::examples!(Day04: abcdef, pqrstuv);
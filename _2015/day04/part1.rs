@@ -1,18 +1,10 @@
-use _2015_day04::{Md5HexIter, KEY};
-use std::time::{Duration, Instant};
+use _2015_day04::Day04;
+use aoc_core::{Solver, input};
 
-fn main() {
-    let start = Instant::now();
-
-    let mut md5_iter = Md5HexIter::new(KEY.to_string(), 1..);
-    while let Some(md5) = md5_iter.next() {
-        /*The third byte must be less than `0x10` (i.e., its high nibble is 0), which ensures the 5th hex digit is `0` while the 6th can be anything.*/
-        if md5[0] == 0 && md5[1] == 0 && md5[2] < 0x10 {
-            println!("{}", md5_iter.current);
-            break;
-        }
-    }
-
-    let elapsed: Duration = start.elapsed();
-    println!("{elapsed:?}")
+fn main() -> Result<(), aoc_core::Error> {
+    let input_file_content =
+        input::read(Day04::YEAR, Day04::DAY, std::env::args().nth(1).as_deref())?;
+    let key = Day04::parse(&input_file_content)?;
+    println!("{}", Day04::part1(&key));
+    Ok(())
 }
@@ -1,3 +1,4 @@
+use aoc_core::{Answer, Error, Solver};
 use std::collections::HashMap;
 
 // part 1
@@ -9,7 +10,7 @@ pub fn contains_at_least_three_vowels(s: &str) -> bool {
     s.chars().any(|c| {
         if ALLOWED_VOWELS.contains(&c) {
             count += 1;
-            if count == 3 { return true } else { false }
+            count == 3
         } else {
             false
         }
@@ -20,7 +21,7 @@ pub fn contains_at_least_one_letter_that_appears_twice_in_a_row(s: &str) -> bool
     let mut last_char: char = std::default::Default::default();
     s.chars().any(|c| {
         if c == last_char {
-            return true;
+            true
         } else {
             last_char = c;
             false
@@ -64,14 +65,14 @@ pub fn contains_any_two_letters_pair_twice_without_overlapping(s: &str) -> bool
             if let [(idx1, char1), (_, char2)] = two_letters {
                 if let Some(first_appearing) = any_two_letters_appearence.get(&(*char1, *char2)) {
                     if idx1 - 1 > *first_appearing {
-                        return true;
+                        true
                     } else {
                         any_two_letters_appearence.insert((*char1, *char2), *idx1);
-                        return false;
+                        false
                     }
                 } else {
                     any_two_letters_appearence.insert((*char1, *char2), *idx1);
-                    return false;
+                    false
                 }
             } else {
                 false
@@ -85,13 +86,55 @@ pub fn contains_repeating_letter_with_one_between(s: &str) -> bool {
         .windows(3)
         .any(|three_letters_sandwich| {
             if let [a, _, c] = three_letters_sandwich {
-                if a == c { true } else { false }
+                a == c
             } else {
                 false
             }
         })
 }
 
+pub struct Day05;
+
+impl Solver for Day05 {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 5;
+
+    type Input<'a> = Vec<&'a str>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        Ok(input.lines().collect())
+    }
+
+    fn part1(lines: &Self::Input<'_>) -> Answer {
+        lines
+            .iter()
+            .filter(|line| {
+                contains_at_least_three_vowels(line)
+                    && contains_at_least_one_letter_that_appears_twice_in_a_row(line)
+                    && does_not_contain_excluded_strings(line)
+            })
+            .count()
+            .into()
+    }
+
+    fn part2(lines: &Self::Input<'_>) -> Answer {
+        lines
+            .iter()
+            .filter(|line| {
+                contains_any_two_letters_pair_twice_without_overlapping(line)
+                    && contains_repeating_letter_with_one_between(line)
+            })
+            .count()
+            .into()
+    }
+}
+
+aoc_core::examples!(
+    Day05:
+    nice_vowels, nice_overlap, no_double, forbidden_pair, one_vowel, nice_pair_and_repeat,
+    nice_short, no_repeat, no_pair,
+);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,22 +1,11 @@
-use _2015_day05::{
-    contains_any_two_letters_pair_twice_without_overlapping,
-    contains_repeating_letter_with_one_between,
-};
+use _2015_day05::Day05;
+use aoc_core::{Solver, input};
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let input_file_content = std::fs::read_to_string("_2015/day05/input.txt")?;
-    let result = input_file_content
-        .lines()
-        .filter(|line| {
-            if contains_any_two_letters_pair_twice_without_overlapping(line)
-                && contains_repeating_letter_with_one_between(line)
-            {
-                true
-            } else {
-                false
-            }
-        })
-        .count();
+fn main() -> Result<(), aoc_core::Error> {
+    let input_file_content =
+        input::read(Day05::YEAR, Day05::DAY, std::env::args().nth(1).as_deref())?;
+    let lines = Day05::parse(&input_file_content)?;
+    let result = Day05::part2(&lines);
     println!("{result}");
     Ok(())
 }
@@ -1,4 +1,5 @@
-use std::collections::HashMap;
+use _2015::{ErrorKind, ParseError, Point, parse};
+use aoc_core::{Answer, Error, Solver};
 
 #[derive(PartialEq, Debug)]
 pub enum Instruction {
@@ -7,22 +8,38 @@ pub enum Instruction {
     Toggle,
 }
 impl TryFrom<&str> for Instruction {
-    type Error = String;
+    type Error = ErrorKind;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         match value {
             "turn on" => Ok(Instruction::TurnOn),
             "turn off" => Ok(Instruction::TurnOff),
             "toggle" => Ok(Instruction::Toggle),
-            _ => Err("Unknown instruction".to_string()),
+            _ => Err(ErrorKind::UnexpectedToken {
+                expected: "'turn on', 'turn off' or 'toggle'",
+            }),
         }
     }
 }
 
+/// Side length of the square grid of lights.
+pub const SIDE: usize = 1000;
+
 #[derive(PartialEq, Debug, Default)]
 pub struct Area {
-    pub from: (u16, u16),
-    pub to: (u16, u16),
+    pub from: Point,
+    pub to: Point,
+}
+
+impl Area {
+    /// The area spans at least one light and lies entirely within the grid.
+    pub fn is_valid(&self) -> bool {
+        let on_grid = |p: Point| (0..SIDE as i32).contains(&p.x) && (0..SIDE as i32).contains(&p.y);
+        self.from.x <= self.to.x
+            && self.from.y <= self.to.y
+            && on_grid(self.from)
+            && on_grid(self.to)
+    }
 }
 
 #[derive(PartialEq, Debug)]
@@ -31,19 +48,20 @@ pub struct GridWork {
     pub action: Instruction,
 }
 
+#[derive(Clone, Default)]
 pub struct Brightness(pub u16);
 
-pub struct Grid(HashMap<(u16, u16), Brightness>);
+pub struct Grid(_2015::Grid<Brightness>);
+
+impl Default for Grid {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl Grid {
     pub fn new() -> Grid {
-        let mut grid = HashMap::<(u16, u16), Brightness>::new();
-        for x in 0..1000 {
-            for y in 0..1000 {
-                grid.insert((x, y), Brightness(0));
-            }
-        }
-        Grid(grid)
+        Grid(_2015::Grid::new(SIDE, SIDE))
     }
 
     pub fn apply<Translation>(
@@ -55,14 +73,13 @@ impl Grid {
         Translation: Fn(&Instruction, &mut Brightness),
     {
         match grid_work.area {
-            Area {
-                from: (x0, y0),
-                to: (x1, y1),
-            } if x0 <= x1 && y0 <= y1 => {
-                for x in x0..(x1 + 1) {
-                    for y in y0..(y1 + 1) {
-                        let brightness: &mut Brightness =
-                            self.0.get_mut(&(x, y)).expect("this should not happen");
+            Area { from, to } if grid_work.area.is_valid() => {
+                for x in from.x..(to.x + 1) {
+                    for y in from.y..(to.y + 1) {
+                        let brightness: &mut Brightness = self
+                            .0
+                            .get_mut(Point::new(x, y))
+                            .expect("valid areas lie within the grid");
                         effect(&grid_work.action, brightness)
                     }
                 }
@@ -77,7 +94,7 @@ impl Grid {
     }
 }
 
-pub fn parse_instruction(s: &str) -> Result<GridWork, String> {
+pub fn parse_instruction(s: &str) -> Result<GridWork, ParseError> {
     let (action_str, area_str) = if let Some(rest) = s.strip_prefix("turn on") {
         ("turn on", rest)
     } else if let Some(rest) = s.strip_prefix("turn off") {
@@ -85,58 +102,105 @@ pub fn parse_instruction(s: &str) -> Result<GridWork, String> {
     } else if let Some(rest) = s.strip_prefix("toggle") {
         ("toggle", rest)
     } else {
-        return Err("could not parse instruction".to_string());
+        let word = s.split(' ').next().unwrap_or(s);
+        return Err(ParseError::at_token(
+            ErrorKind::UnexpectedToken {
+                expected: "'turn on', 'turn off' or 'toggle'",
+            },
+            s,
+            word,
+        ));
     };
 
     let action = Instruction::try_from(action_str)?;
-    let area = get_area(area_str)?;
+    let area = get_area(area_str).map_err(|e| e.within(s, area_str))?;
 
     Ok(GridWork { action, area })
 }
 
-fn get_area(s: &str) -> Result<Area, String> {
-    let mut area: Area = Default::default();
-    let mut coord_iter = s.split("through").map(str::trim);
-    match coord_iter.next() {
-        Some(s) => {
-            let mut start_coord_iter = s.split(',').map(str::parse::<u16>);
-            match start_coord_iter.next() {
-                Some(Ok(x)) => area.from.0 = x,
-                Some(Err(e)) => return Err(e.to_string()),
-                None => return Err("expected start x coordinate".to_string()),
-            };
-            match start_coord_iter.next() {
-                Some(Ok(y)) => area.from.1 = y,
-                Some(Err(e)) => return Err(e.to_string()),
-                None => return Err("expected start y coordinate".to_string()),
-            };
-        },
-        None => return Err("expected start coordinates".to_string()),
-    };
+fn get_area(s: &str) -> Result<Area, ParseError> {
+    let point = |p: &str| p.trim().parse::<Point>().map_err(|e| e.within(s, p.trim()));
+    match s.split_once("through") {
+        Some((from, to)) => Ok(Area {
+            from: point(from)?,
+            to: point(to)?,
+        }),
+        None => Err(ParseError::at_end(
+            ErrorKind::MissingToken {
+                expected: "'x0,y0 through x1,y1'",
+            },
+            s,
+        )),
+    }
+}
 
-    match coord_iter.next() {
-        Some(s) => {
-            let mut end_coord_iter = s.split(',').map(str::parse::<u16>);
-            match end_coord_iter.next() {
-                Some(Ok(x)) => area.to.0 = x,
-                Some(Err(e)) => return Err(e.to_string()),
-                None => return Err("expected end x coordinate".to_string()),
-            };
-            match end_coord_iter.next() {
-                Some(Ok(y)) => area.to.1 = y,
-                Some(Err(e)) => return Err(e.to_string()),
-                None => return Err("expected end y coordinate".to_string()),
-            };
+pub fn light_up(instruction: &Instruction, brightness: &mut Brightness) {
+    match instruction {
+        Instruction::TurnOn => *brightness = Brightness(1),
+        Instruction::TurnOff => *brightness = Brightness(0),
+        Instruction::Toggle => match brightness {
+            Brightness(1) => {
+                *brightness = Brightness(0);
+            }
+            Brightness(0) => {
+                *brightness = Brightness(1);
+            }
+            _ => (),
         },
-        None => return Err("expected end coordinates".to_string()),
-    };
+    }
+}
+
+pub fn adjust_brightness(instruction: &Instruction, brightness: &mut Brightness) {
+    match instruction {
+        Instruction::TurnOn => brightness.0 += 1,
+        Instruction::TurnOff => brightness.0 = brightness.0.saturating_sub(1),
+        Instruction::Toggle => brightness.0 += 2,
+    }
+}
 
-    match coord_iter.next() {
-        Some(_) => Err("invalid coordinates".to_string()),
-        None => Ok(area),
+fn follow_instructions<Translation>(instructions: &[GridWork], effect: Translation) -> u32
+where
+    Translation: Fn(&Instruction, &mut Brightness),
+{
+    let mut grid = Grid::new();
+    for grid_work in instructions {
+        grid.apply(grid_work, &effect)
+            .expect("instructions are validated by parse");
     }
+    grid.total_brightness()
 }
 
+pub struct Day06;
+
+impl Solver for Day06 {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 6;
+
+    type Input<'a> = Vec<GridWork>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        Ok(parse::lines(input, |line| {
+            let grid_work = parse_instruction(line)?;
+            match grid_work.area.is_valid() {
+                true => Ok(grid_work),
+                false => Err(ParseError::from(ErrorKind::Invalid(
+                    "area outside of the grid".to_string(),
+                ))),
+            }
+        })?)
+    }
+
+    fn part1(instructions: &Self::Input<'_>) -> Answer {
+        follow_instructions(instructions, light_up).into()
+    }
+
+    fn part2(instructions: &Self::Input<'_>) -> Answer {
+        follow_instructions(instructions, adjust_brightness).into()
+    }
+}
+
+aoc_core::examples!(Day06: turn_on_all, toggle_first_line, turn_off_middle, turn_on_one, toggle_all);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -148,13 +212,22 @@ mod tests {
         assert_eq!(
             get_area(valid_input),
             Ok(Area {
-                from: (660, 55),
-                to: (986, 197)
+                from: Point::new(660, 55),
+                to: Point::new(986, 197)
             })
         );
         assert!(get_area(invalid_input).is_err());
     }
 
+    #[test]
+    fn test_parse_instruction_points_at_invalid_coordinate() {
+        let error = parse_instruction("turn on 0,x through 2,2").unwrap_err();
+        assert!(matches!(error.kind, ErrorKind::InvalidNumber(_)));
+        assert_eq!(error.span, 10..11);
+        let error = parse_instruction("switch 0,0 through 2,2").unwrap_err();
+        assert_eq!(error.span, 0..6);
+    }
+
     #[test]
     fn test_parse_instruction() {
         let valid_input = "turn off 499,499 through 500,500";
@@ -162,11 +235,32 @@ mod tests {
             parse_instruction(valid_input),
             Ok(GridWork {
                 area: Area {
-                    from: (499, 499),
-                    to: (500, 500)
+                    from: Point::new(499, 499),
+                    to: Point::new(500, 500)
                 },
                 action: Instruction::TurnOff
             })
         )
     }
+
+    #[test]
+    fn test_grid_apply_instruction() {
+        let mut grid = Grid::new();
+        let grid_work = GridWork {
+            area: Area {
+                from: Point::new(0, 0),
+                to: Point::new(999, 999),
+            },
+            action: Instruction::TurnOn,
+        };
+        grid.apply(&grid_work, light_up).unwrap();
+        assert_eq!(grid.total_brightness(), 1_000_000)
+    }
+
+    #[test]
+    fn test_solver_rejects_area_outside_of_the_grid() {
+        assert!(Day06::parse("turn on 0,0 through 999,999").is_ok());
+        assert!(Day06::parse("turn on 0,0 through 999,1000").is_err());
+        assert!(Day06::parse("toggle 5,5 through 4,4").is_err());
+    }
 }
@@ -1,3 +1,5 @@
+use _2015::{ErrorKind, ParseError, parse};
+use aoc_core::{Answer, Error, Solver};
 use std::{
     collections::HashMap,
     fmt::Display,
@@ -94,148 +96,175 @@ impl<'a> Display for Instruction<'a> {
     }
 }
 
+/// The space separated tokens of an instruction, errors point at the offending one.
+struct Tokens<'a> {
+    line: &'a str,
+    split: std::str::Split<'a, char>,
+}
+
+impl<'a> Tokens<'a> {
+    fn new(line: &'a str) -> Self {
+        Self {
+            line,
+            split: line.split(' '),
+        }
+    }
+
+    fn next(&mut self) -> Option<&'a str> {
+        self.split.next()
+    }
+
+    fn missing(&self, expected: &'static str) -> ParseError {
+        ParseError::at_end(ErrorKind::MissingToken { expected }, self.line)
+    }
+
+    fn unexpected(&self, expected: &'static str, token: &str) -> ParseError {
+        ParseError::at_token(ErrorKind::UnexpectedToken { expected }, self.line, token)
+    }
+
+    fn wire(&mut self) -> Result<&'a str, ParseError> {
+        match self.split.next() {
+            Some("") | None => Err(self.missing("a wire id")),
+            Some(wire) => Ok(wire),
+        }
+    }
+
+    /// Parses the number of bits a shift gate moves a signal by, below the width of a wire.
+    fn shift(&mut self) -> Result<u16, ParseError> {
+        let token = self.split.next().ok_or_else(|| self.missing("a number"))?;
+        let shift: u16 = parse::number(self.line, token)?;
+        if u32::from(shift) >= u16::BITS {
+            let kind = ErrorKind::Invalid(format!("cannot shift a 16 bit signal by {shift}"));
+            return Err(ParseError::at_token(kind, self.line, token));
+        }
+        Ok(shift)
+    }
+
+    /// Parses the `-> wire` ending every instruction.
+    fn target(&mut self) -> Result<&'a str, ParseError> {
+        match self.split.next() {
+            Some("->") => {}
+            Some(token) => return Err(self.unexpected("'->'", token)),
+            None => return Err(self.missing("'->'")),
+        }
+        self.last_wire()
+    }
+
+    /// Parses the wire a signal is sent to, which ends the instruction.
+    fn last_wire(&mut self) -> Result<&'a str, ParseError> {
+        let wire = self.wire()?;
+        match self.split.next() {
+            Some(extra) => Err(self.unexpected("the end of the instruction", extra)),
+            None => Ok(wire),
+        }
+    }
+}
+
 // parser for instructions
 impl<'a> TryFrom<&'a str> for Instruction<'a> {
-    type Error = &'a str;
+    type Error = ParseError;
     fn try_from(value: &'a str) -> Result<Self, Self::Error> {
-        let wire_err: &'static str = "expected a wire id here";
-        let operator_err: &'static str = "expected '->' sign here";
-        let number_err: &'static str = "expected a number here";
-        let number_parse_err: &'static str = "failed to parse number";
-        let incomplete_instruction: &'static str = "incomplete instruction";
-        let unknown_token: &'static str = "unknown token";
-
-        let mut split = value.split(' ');
-        match split.next() {
+        let mut tokens = Tokens::new(value);
+        match tokens.next() {
             Some(number) if number.starts_with(char::is_numeric) => {
-                let val = number.parse::<u16>().map_err(|_| number_parse_err)?;
-                match split.next() {
+                let val = parse::number(value, number)?;
+                match tokens.next() {
                     Some("AND") => {
-                        let wire_operand = split.next().ok_or(wire_err)?;
-                        split.next().ok_or(operator_err)?;
-                        let wire = split.next().ok_or(wire_err)?;
-                        return Ok(Instruction {
-                            wire: wire,
+                        let wire_operand = tokens.wire()?;
+                        let wire = tokens.target()?;
+                        Ok(Instruction {
+                            wire,
                             source: SignalSource::FromValueAndGate {
                                 value: val,
                                 wire_id: wire_operand,
                             },
-                        });
+                        })
                     }
                     Some("->") => {
-                        let wire = split.next().ok_or(wire_err)?;
-                        return Ok(Instruction {
+                        let wire = tokens.last_wire()?;
+                        Ok(Instruction {
                             wire,
                             source: SignalSource::FromValue(val),
-                        });
+                        })
                     }
-                    Some(unknown) => {
-                        dbg!(unknown);
-                        return Err(unknown_token);
-                    }
-                    None => {
-                        return Err(incomplete_instruction)
-                    },
-                };
+                    Some(unknown) => Err(tokens.unexpected("AND or '->'", unknown)),
+                    None => Err(tokens.missing("AND or '->'")),
+                }
             }
             Some("NOT") => {
-                let wire_operand = split.next().ok_or(wire_err)?;
-                split.next().ok_or(operator_err)?;
-                let wire = split.next().ok_or(wire_err)?;
-                return Ok(Instruction {
-                    wire: wire,
+                let wire_operand = tokens.wire()?;
+                let wire = tokens.target()?;
+                Ok(Instruction {
+                    wire,
                     source: SignalSource::FromNotGate {
                         wire_id: wire_operand,
                     },
-                });
-            }
-            Some(wire_operand) => {
-                match split.next() {
-                    Some("AND") => {
-                        let wire_operand2 = split.next().ok_or(wire_err)?;
-                        split.next().ok_or(operator_err)?;
-                        let wire = split.next().ok_or(wire_err)?;
-                        return Ok(Instruction {
-                            wire: wire,
-                            source: SignalSource::FromAndGate {
-                                wire_id1: wire_operand,
-                                wire_id2: wire_operand2,
-                            },
-                        });
-                    }
-                    Some("OR") => {
-                        let wire_operand2 = split.next().ok_or(wire_err)?;
-                        split.next().ok_or(operator_err)?;
-                        let wire = split.next().ok_or(wire_err)?;
-                        return Ok(Instruction {
-                            wire: wire,
-                            source: SignalSource::FromOrGate {
-                                wire_id1: wire_operand,
-                                wire_id2: wire_operand2,
-                            },
-                        });
-                    }
-                    Some("LSHIFT") => {
-                        let value_operand = split
-                            .next()
-                            .ok_or(number_err)?
-                            .parse::<u16>()
-                            .map_err(|_| number_parse_err)?;
-                        split.next().ok_or(operator_err)?;
-                        let wire = split.next().ok_or(wire_err)?;
-                        return Ok(Instruction {
-                            wire: wire,
-                            source: SignalSource::FromLShiftGate {
-                                wire_id: wire_operand,
-                                value: value_operand,
-                            },
-                        });
-                    }
-                    Some("RSHIFT") => {
-                        let value_operand = split
-                            .next()
-                            .ok_or(number_err)?
-                            .parse::<u16>()
-                            .map_err(|_| number_parse_err)?;
-                        split.next().ok_or(operator_err)?;
-                        let wire = split.next().ok_or(wire_err)?;
-                        return Ok(Instruction {
-                            wire: wire,
-                            source: SignalSource::FromRShiftGate {
-                                wire_id: wire_operand,
-                                value: value_operand,
-                            },
-                        });
-                    }
-                    Some("->") => {
-                        let wire = split.next().ok_or(wire_err)?;
-                        return Ok(Instruction {
-                            wire: wire,
-                            source: SignalSource::FromWire {
-                                wire_id: wire_operand,
-                            },
-                        });
-                    }
-                    Some(unknown) => {
-                        dbg!(unknown);
-                        return Err(unknown_token);
-                    }
-                    None => return Err(incomplete_instruction),
-                };
-            }
-            None => {
-                return Err("empty instruction");
+                })
             }
+            Some("") | None => Err(tokens.missing("an instruction")),
+            Some(wire_operand) => match tokens.next() {
+                Some("AND") => {
+                    let wire_operand2 = tokens.wire()?;
+                    let wire = tokens.target()?;
+                    Ok(Instruction {
+                        wire,
+                        source: SignalSource::FromAndGate {
+                            wire_id1: wire_operand,
+                            wire_id2: wire_operand2,
+                        },
+                    })
+                }
+                Some("OR") => {
+                    let wire_operand2 = tokens.wire()?;
+                    let wire = tokens.target()?;
+                    Ok(Instruction {
+                        wire,
+                        source: SignalSource::FromOrGate {
+                            wire_id1: wire_operand,
+                            wire_id2: wire_operand2,
+                        },
+                    })
+                }
+                Some("LSHIFT") => {
+                    let value_operand = tokens.shift()?;
+                    let wire = tokens.target()?;
+                    Ok(Instruction {
+                        wire,
+                        source: SignalSource::FromLShiftGate {
+                            wire_id: wire_operand,
+                            value: value_operand,
+                        },
+                    })
+                }
+                Some("RSHIFT") => {
+                    let value_operand = tokens.shift()?;
+                    let wire = tokens.target()?;
+                    Ok(Instruction {
+                        wire,
+                        source: SignalSource::FromRShiftGate {
+                            wire_id: wire_operand,
+                            value: value_operand,
+                        },
+                    })
+                }
+                Some("->") => {
+                    let wire = tokens.last_wire()?;
+                    Ok(Instruction {
+                        wire,
+                        source: SignalSource::FromWire {
+                            wire_id: wire_operand,
+                        },
+                    })
+                }
+                Some(unknown) => Err(tokens.unexpected("a gate or '->'", unknown)),
+                None => Err(tokens.missing("a gate or '->'")),
+            },
         }
     }
 }
 
-pub fn parse_instructions(input: &str) -> Result<Vec<Instruction<'_>>, &str> {
-    input
-        .trim()
-        .lines()
-        .map(|l| Instruction::try_from(l))
-        .collect::<Result<Vec<_>, _>>()
+pub fn parse_instructions(input: &str) -> Result<Vec<Instruction<'_>>, ParseError> {
+    parse::lines(input.trim_end(), Instruction::try_from)
 }
 
 pub fn circuit_map<'a>(instructions: Vec<Instruction<'a>>) -> HashMap<&'a str, SignalSource<'a>> {
@@ -245,15 +274,19 @@ pub fn circuit_map<'a>(instructions: Vec<Instruction<'a>>) -> HashMap<&'a str, S
         .collect::<HashMap<&str, SignalSource<'a>>>()
 }
 
+/// Passes signals through the circuit until `condition` holds, or until a pass gets no new
+/// signal through, when wires depend on each other or on a wire nothing drives.
 pub fn calculate_signals_until<'a, F>(
     circuit_map: &mut HashMap<&'a str, SignalSource<'a>>,
     condition: F,
-) -> HashMap<&'a str, SignalSource<'a>>
+) -> Result<HashMap<&'a str, SignalSource<'a>>, ErrorKind>
 where
     F: Fn(&mut HashMap<&'a str, SignalSource<'a>>) -> bool,
 {
     let mut resolved: HashMap<&str, SignalSource> = HashMap::new();
     loop {
+        let resolved_before = resolved.len();
+        let mut gates_reduced = false;
         for (k, v) in circuit_map.iter_mut() {
             match v {
                 SignalSource::FromValue(_) => {
@@ -275,13 +308,15 @@ where
                     if let Some(SignalSource::FromValue(val)) = resolved.get(wire_id1) {
                         *v = SignalSource::FromValueAndGate {
                             value: *val,
-                            wire_id: &wire_id2,
+                            wire_id: wire_id2,
                         };
+                        gates_reduced = true;
                     } else if let Some(SignalSource::FromValue(val)) = resolved.get(wire_id2) {
                         *v = SignalSource::FromValueAndGate {
                             value: *val,
-                            wire_id: &wire_id1,
+                            wire_id: wire_id1,
                         };
+                        gates_reduced = true;
                     }
                 }
                 SignalSource::FromValueOrGate { value, wire_id } => {
@@ -294,13 +329,15 @@ where
                     if let Some(SignalSource::FromValue(val)) = resolved.get(wire_id1) {
                         *v = SignalSource::FromValueOrGate {
                             value: *val,
-                            wire_id: &wire_id2,
+                            wire_id: wire_id2,
                         };
+                        gates_reduced = true;
                     } else if let Some(SignalSource::FromValue(val)) = resolved.get(wire_id2) {
                         *v = SignalSource::FromValueOrGate {
                             value: *val,
-                            wire_id: &wire_id1,
+                            wire_id: wire_id1,
                         };
+                        gates_reduced = true;
                     }
                 }
                 SignalSource::FromLShiftGate { wire_id, value } => {
@@ -325,16 +362,83 @@ where
         }
 
         if condition(circuit_map) {
-            break;
+            return Ok(resolved);
+        }
+        if resolved.len() == resolved_before && !gates_reduced {
+            return Err(ErrorKind::Invalid(
+                "the signals are stuck, wires depend on each other or on a wire nothing drives"
+                    .to_string(),
+            ));
         }
     }
-    resolved
 }
 
 pub fn a_found(circuit_map: &mut HashMap<&str, SignalSource<'_>>) -> bool {
     matches!(circuit_map.get("a"), Some(SignalSource::FromValue(_)))
 }
 
+pub fn signal_on(circuit_map: &HashMap<&str, SignalSource<'_>>, wire: &str) -> Option<u16> {
+    match circuit_map.get(wire) {
+        Some(SignalSource::FromValue(value)) => Some(*value),
+        _ => None,
+    }
+}
+
+/// A circuit known to send a signal to wire `a`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Circuit<'a> {
+    pub wires: HashMap<&'a str, SignalSource<'a>>,
+    /// the signal wire `a` gets once the circuit settles
+    pub a: u16,
+}
+
+impl<'a> Circuit<'a> {
+    pub fn new(wires: HashMap<&'a str, SignalSource<'a>>) -> Result<Circuit<'a>, ErrorKind> {
+        if !wires.contains_key("a") {
+            return Err(ErrorKind::Invalid(
+                "no instruction sends a signal to wire a".to_string(),
+            ));
+        }
+        let a = signal_on_a(wires.clone())?;
+        Ok(Circuit { wires, a })
+    }
+}
+
+fn signal_on_a<'a>(mut wires: HashMap<&'a str, SignalSource<'a>>) -> Result<u16, ErrorKind> {
+    calculate_signals_until(&mut wires, a_found)?;
+    Ok(signal_on(&wires, "a").expect("signals are calculated until a is found"))
+}
+
+pub struct Day07;
+
+impl Solver for Day07 {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 7;
+
+    type Input<'a> = Circuit<'a>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        Ok(Circuit::new(circuit_map(parse_instructions(input)?))?)
+    }
+
+    fn part1(circuit: &Self::Input<'_>) -> Answer {
+        circuit.a.into()
+    }
+
+    fn part2(circuit: &Self::Input<'_>) -> Answer {
+        // override wire "b" to signal from "a"
+        let mut wires = circuit.wires.clone();
+        wires
+            .entry("b")
+            .and_modify(|signal| *signal = SignalSource::FromValue(circuit.a));
+        signal_on_a(wires)
+            .expect("a signal on b only lets more signals through")
+            .into()
+    }
+}
+
+aoc_core::examples!(Day07: published_wires, shifted_b);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -393,4 +497,49 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn test_try_from_points_at_offending_token() {
+        let error = Instruction::try_from("x AND y => d").unwrap_err();
+        assert_eq!(error.kind, ErrorKind::UnexpectedToken { expected: "'->'" });
+        assert_eq!(error.span, 8..10);
+        let error = Instruction::try_from("x LSHIFT a -> f").unwrap_err();
+        assert!(matches!(error.kind, ErrorKind::InvalidNumber(_)));
+        assert_eq!(error.span, 9..10);
+        let error = Instruction::try_from("x LSHIFT 16 -> f").unwrap_err();
+        assert_eq!(
+            error.kind,
+            ErrorKind::Invalid("cannot shift a 16 bit signal by 16".to_string())
+        );
+        assert_eq!(error.span, 9..11);
+        assert!(Instruction::try_from("x RSHIFT 15 -> f").is_ok());
+        let error = Instruction::try_from("1 ->").unwrap_err();
+        assert_eq!(error.span, 4..4);
+    }
+
+    #[test]
+    fn test_solver() {
+        let circuit = Day07::parse("123 -> x\nx LSHIFT 2 -> b\nNOT b -> a").unwrap();
+        assert_eq!(Day07::part1(&circuit), Answer::from(!(123u16 << 2)));
+        assert_eq!(Day07::part2(&circuit), Answer::from(!(!(123u16 << 2))));
+        assert!(Day07::parse("x XOR y -> z").is_err());
+    }
+
+    #[test]
+    fn test_unsolvable_circuits() {
+        let error = Day07::parse("123 -> x").unwrap_err();
+        assert_eq!(error.to_string(), "no instruction sends a signal to wire a");
+        // wires depending on each other, or on a wire nothing drives
+        for input in [
+            "b -> a\na -> b",
+            "1 -> x\nx AND b -> a\nNOT a -> b",
+            "x -> a",
+        ] {
+            let error = Day07::parse(input).unwrap_err();
+            assert!(
+                error.to_string().starts_with("the signals are stuck"),
+                "{input:?}"
+            );
+        }
+    }
 }
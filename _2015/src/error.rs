@@ -0,0 +1,266 @@
+use std::{
+    error::Error,
+    fmt::{Display, Write},
+    num::ParseIntError,
+    ops::Range,
+};
+
+/// Lines longer than this are cut around the offending span when pretty printed.
+const MAX_SHOWN_WIDTH: usize = 80;
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum ErrorKind {
+    /// a character outside of the puzzle's alphabet
+    UnexpectedChar(char),
+    /// a token other than the one expected at this position
+    UnexpectedToken {
+        expected: &'static str,
+    },
+    /// the line ended before the expected token
+    MissingToken {
+        expected: &'static str,
+    },
+    InvalidNumber(ParseIntError),
+    /// a number that failed to parse, named after what it stands for
+    InvalidField {
+        field: &'static str,
+        source: ParseIntError,
+    },
+    /// a well formed line the puzzle does not allow
+    Invalid(String),
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ErrorKind::UnexpectedChar(c) => write!(f, "unexpected character {c:?}"),
+            ErrorKind::UnexpectedToken { expected } => write!(f, "expected {expected}"),
+            ErrorKind::MissingToken { expected } => write!(f, "missing {expected}"),
+            ErrorKind::InvalidNumber(e) => write!(f, "invalid number: {e}"),
+            ErrorKind::InvalidField { field, source } => write!(f, "invalid {field}: {source}"),
+            ErrorKind::Invalid(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+/// An error raised while parsing a puzzle input, pointing at the offending part of a line.
+///
+/// `{}` prints a one line summary, `{:#}` also shows the line with the span underlined:
+///
+/// ```text
+/// line 3, column 9: expected '->'
+///   |
+/// 3 | x AND y => d
+///   |         ^^
+/// ```
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct ParseError {
+    pub kind: ErrorKind,
+    /// 1-based line number, once known
+    pub line: Option<usize>,
+    /// the offending line
+    pub text: String,
+    /// byte range of the offending part of `text`
+    pub span: Range<usize>,
+}
+
+/// Byte offset of `sub` within `line`, `sub` must be a slice of `line`.
+fn offset_in(line: &str, sub: &str) -> usize {
+    let offset = (sub.as_ptr() as usize).wrapping_sub(line.as_ptr() as usize);
+    assert!(offset <= line.len(), "{sub:?} is not a slice of {line:?}");
+    offset
+}
+
+impl ParseError {
+    pub fn new(kind: ErrorKind, text: &str, span: Range<usize>) -> ParseError {
+        ParseError {
+            kind,
+            line: None,
+            text: text.to_string(),
+            span,
+        }
+    }
+
+    /// An error about `token`, a slice of `line`.
+    pub fn at_token(kind: ErrorKind, line: &str, token: &str) -> ParseError {
+        let start = offset_in(line, token);
+        ParseError::new(kind, line, start..start + token.len())
+    }
+
+    /// An error about something missing at the end of `line`.
+    pub fn at_end(kind: ErrorKind, line: &str) -> ParseError {
+        ParseError::new(kind, line, line.len()..line.len())
+    }
+
+    /// An error without a location yet, it covers the whole line once `on_line` is called.
+    pub fn unlocated(kind: ErrorKind) -> ParseError {
+        ParseError::new(kind, "", 0..0)
+    }
+
+    /// Places an error raised on `sub`, a slice of `line`, within `line`.
+    pub fn within(self, line: &str, sub: &str) -> ParseError {
+        if self.text.is_empty() {
+            return ParseError::at_token(self.kind, line, sub);
+        }
+        let offset = offset_in(line, sub);
+        ParseError {
+            text: line.to_string(),
+            span: self.span.start + offset..self.span.end + offset,
+            ..self
+        }
+    }
+
+    /// Records the 1-based number and text of the line the error was raised on.
+    pub fn on_line(mut self, number: usize, line: &str) -> ParseError {
+        if self.text.is_empty() {
+            self.text = line.to_string();
+            self.span = 0..line.len();
+        }
+        self.line = Some(number);
+        self
+    }
+
+    /// 1-based column of the start of the span, in characters.
+    pub fn column(&self) -> usize {
+        self.text[..self.span.start.min(self.text.len())]
+            .chars()
+            .count()
+            + 1
+    }
+
+    fn write_pretty(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let start = self.span.start.min(self.text.len());
+        let end = self.span.end.clamp(start, self.text.len());
+        let before: Vec<char> = self.text[..start].chars().collect();
+        let spanned = self.text[start..end].chars().count();
+        let after: Vec<char> = self.text[end..].chars().collect();
+
+        // keep some context on each side of the span on long lines
+        let context = MAX_SHOWN_WIDTH.saturating_sub(spanned) / 2;
+        let (cut_before, cut_after) = if before.len() + spanned + after.len() > MAX_SHOWN_WIDTH {
+            (
+                before.len().saturating_sub(context),
+                after.len().min(context),
+            )
+        } else {
+            (0, after.len())
+        };
+        let mut shown = String::new();
+        let mut caret_offset = before.len() - cut_before;
+        if cut_before > 0 {
+            shown.push_str("...");
+            caret_offset += 3;
+        }
+        shown.extend(&before[cut_before..]);
+        shown.push_str(&self.text[start..end]);
+        shown.extend(&after[..cut_after]);
+        if cut_after < after.len() {
+            shown.push_str("...");
+        }
+
+        let number = self.line.map(|n| n.to_string()).unwrap_or_default();
+        let gutter = " ".repeat(number.len());
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{number} | {shown}")?;
+        let mut carets = " ".repeat(caret_offset);
+        for _ in 0..spanned.max(1) {
+            carets.push('^');
+        }
+        write!(f, "{gutter} | {carets}")
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut location = String::new();
+        if let Some(line) = self.line {
+            write!(location, "line {line}, ")?;
+        }
+        write!(f, "{location}column {}: {}", self.column(), self.kind)?;
+        if f.alternate() {
+            writeln!(f)?;
+            self.write_pretty(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ErrorKind {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ErrorKind::InvalidNumber(e) | ErrorKind::InvalidField { source: e, .. } => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.kind.source()
+    }
+}
+
+impl From<ErrorKind> for ParseError {
+    fn from(kind: ErrorKind) -> Self {
+        ParseError::unlocated(kind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pretty_print_points_at_span() {
+        let line = "x AND y => d";
+        let error = ParseError::at_token(
+            ErrorKind::UnexpectedToken { expected: "'->'" },
+            line,
+            &line[8..10],
+        )
+        .on_line(3, line);
+        assert_eq!(error.to_string(), "line 3, column 9: expected '->'");
+        assert_eq!(
+            format!("{error:#}"),
+            "line 3, column 9: expected '->'\n  |\n3 | x AND y => d\n  |         ^^"
+        );
+    }
+
+    #[test]
+    fn test_pretty_print_missing_token_after_line() {
+        let error = ParseError::at_end(ErrorKind::MissingToken { expected: "a wire" }, "1 ->")
+            .on_line(12, "1 ->");
+        assert_eq!(
+            format!("{error:#}"),
+            "line 12, column 5: missing a wire\n   |\n12 | 1 ->\n   |     ^"
+        );
+    }
+
+    #[test]
+    fn test_pretty_print_cuts_long_lines() {
+        let line = format!("{}x{}", "(".repeat(200), ")".repeat(200));
+        let error = ParseError::new(ErrorKind::UnexpectedChar('x'), &line, 200..201);
+        let pretty = format!("{error:#}");
+        let shown = pretty.lines().nth(2).unwrap();
+        let carets = pretty.lines().nth(3).unwrap();
+        assert!(shown.len() < 100);
+        assert_eq!(
+            shown.find('x').unwrap(),
+            carets.find('^').unwrap(),
+            "{pretty}"
+        );
+    }
+
+    #[test]
+    fn test_within_and_on_line() {
+        let line = "turn on 0,x through 2,2";
+        let point = &line[8..11];
+        let error = ParseError::at_token(ErrorKind::UnexpectedChar('x'), point, &point[2..3])
+            .within(line, point)
+            .on_line(4, line);
+        assert_eq!(error.span, 10..11);
+        assert_eq!(error.line, Some(4));
+        let unlocated = ParseError::from(ErrorKind::Invalid("bad".to_string())).on_line(1, line);
+        assert_eq!(unlocated.span, 0..line.len());
+    }
+}
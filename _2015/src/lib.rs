@@ -0,0 +1,11 @@
+//! Shared logic for the 2015 puzzles.
+
+pub mod error;
+pub mod grid;
+pub mod parse;
+pub mod point;
+pub mod random;
+
+pub use error::{ErrorKind, ParseError};
+pub use grid::Grid;
+pub use point::{Direction, Point};
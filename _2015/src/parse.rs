@@ -0,0 +1,127 @@
+use crate::error::{ErrorKind, ParseError};
+
+/// Parses every line of `input`, reporting the line of the first failure.
+pub fn lines<'a, T, E, F>(input: &'a str, mut parse: F) -> Result<Vec<T>, ParseError>
+where
+    F: FnMut(&'a str) -> Result<T, E>,
+    E: Into<ParseError>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse(line).map_err(|e| e.into().on_line(i + 1, line)))
+        .collect()
+}
+
+/// Parses every character of `input`, line breaks included, reporting the
+/// line and column of the first failure.
+pub fn chars<T, F>(input: &str, mut parse: F) -> Result<Vec<T>, ParseError>
+where
+    F: FnMut(char) -> Result<T, ErrorKind>,
+{
+    let mut parsed = Vec::with_capacity(input.len());
+    for (i, line) in input.split_inclusive('\n').enumerate() {
+        for (column, c) in line.char_indices() {
+            match parse(c) {
+                Ok(value) => parsed.push(value),
+                Err(kind) => {
+                    let text = line.trim_end_matches(['\n', '\r']);
+                    return Err(ParseError::new(kind, text, column..column + c.len_utf8())
+                        .on_line(i + 1, text));
+                }
+            }
+        }
+    }
+    Ok(parsed)
+}
+
+/// Splits `s` around the first `delimiter`, both sides trimmed and non-empty.
+pub fn split_pair(s: &str, delimiter: char) -> Result<(&str, &str), ParseError> {
+    let missing = |expected| ParseError::at_end(ErrorKind::MissingToken { expected }, s);
+    let (left, right) = s
+        .split_once(delimiter)
+        .ok_or_else(|| missing("a second value"))?;
+    match (left.trim(), right.trim()) {
+        ("", _) => Err(ParseError::at_token(
+            ErrorKind::MissingToken {
+                expected: "a first value",
+            },
+            s,
+            &s[..0],
+        )),
+        (_, "") => Err(missing("a second value")),
+        (left, right) => Ok((left, right)),
+    }
+}
+
+/// Parses `token`, a slice of `line`, as a number.
+pub fn number<T>(line: &str, token: &str) -> Result<T, ParseError>
+where
+    T: std::str::FromStr<Err = std::num::ParseIntError>,
+{
+    token
+        .parse::<T>()
+        .map_err(|e| ParseError::at_token(ErrorKind::InvalidNumber(e), line, token))
+}
+
+/// Like `number`, naming what the number stands for in the error.
+pub fn field<T>(line: &str, token: &str, field: &'static str) -> Result<T, ParseError>
+where
+    T: std::str::FromStr<Err = std::num::ParseIntError>,
+{
+    token.parse::<T>().map_err(|source| {
+        ParseError::at_token(ErrorKind::InvalidField { field, source }, line, token)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines_reports_failing_line() {
+        let parsed = lines("1\n2\nx\n4", |line| number::<u8>(line, line));
+        assert_eq!(parsed.unwrap_err().line, Some(3));
+        assert_eq!(
+            lines("1\n2", |line| number::<u8>(line, line)),
+            Ok(vec![1, 2])
+        );
+    }
+
+    #[test]
+    fn test_chars_reports_line_and_column() {
+        let parse = |c| match c {
+            '(' | ')' => Ok(c),
+            _ => Err(ErrorKind::UnexpectedChar(c)),
+        };
+        assert_eq!(chars("()", parse).unwrap().len(), 2);
+        let error = chars("()\n(x)", parse).unwrap_err();
+        assert_eq!(error.line, Some(1));
+        assert_eq!(error.span, 2..3);
+        assert_eq!(error.kind, ErrorKind::UnexpectedChar('\n'));
+        let error = chars("(x)", parse).unwrap_err();
+        assert_eq!((error.line, error.column()), (Some(1), 2));
+    }
+
+    #[test]
+    fn test_split_pair() {
+        assert_eq!(split_pair("1, 2", ','), Ok(("1", "2")));
+        assert!(split_pair("1,", ',').is_err());
+        assert!(split_pair(",1", ',').is_err());
+        assert!(split_pair("12", ',').is_err());
+    }
+
+    #[test]
+    fn test_field_names_failing_field() {
+        let line = "7 9";
+        assert_eq!(field::<u8>(line, &line[2..], "depth"), Ok(9));
+        let line = "7 300";
+        let error = field::<u8>(line, &line[2..], "depth").unwrap_err();
+        assert_eq!(error.span, 2..5);
+        assert!(
+            error
+                .to_string()
+                .ends_with("invalid depth: number too large to fit in target type")
+        );
+    }
+}
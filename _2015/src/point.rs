@@ -0,0 +1,100 @@
+use std::{
+    ops::{Add, AddAssign},
+    str::FromStr,
+};
+
+use crate::{
+    error::ParseError,
+    parse::{number, split_pair},
+};
+
+/// A location on an unbounded 2D grid, `y` grows towards the north.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: i32, y: i32) -> Point {
+        Point { x, y }
+    }
+
+    pub fn step(self, direction: Direction) -> Point {
+        self + direction.offset()
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+/// Parses `"x,y"`, e.g. `"660,55"`.
+impl FromStr for Point {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (x, y) = split_pair(s, ',')?;
+        Ok(Point::new(number(s, x)?, number(s, y)?))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Direction {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::South,
+        Direction::East,
+        Direction::West,
+    ];
+
+    pub fn offset(self) -> Point {
+        match self {
+            Direction::North => Point::new(0, 1),
+            Direction::South => Point::new(0, -1),
+            Direction::East => Point::new(1, 0),
+            Direction::West => Point::new(-1, 0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step() {
+        let start = Point::new(3, -2);
+        assert_eq!(start.step(Direction::North), Point::new(3, -1));
+        assert_eq!(start.step(Direction::South), Point::new(3, -3));
+        assert_eq!(start.step(Direction::East), Point::new(4, -2));
+        assert_eq!(start.step(Direction::West), Point::new(2, -2));
+    }
+
+    #[test]
+    fn test_parse_point() {
+        assert_eq!("660,55".parse::<Point>(), Ok(Point::new(660, 55)));
+        assert!("660,".parse::<Point>().is_err());
+        assert!("660".parse::<Point>().is_err());
+        assert!("a,1".parse::<Point>().is_err());
+    }
+}
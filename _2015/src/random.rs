@@ -0,0 +1,46 @@
+/// A small xorshift generator, enough to vary generated inputs in tests and benchmarks.
+///
+/// The same seed always gives the same numbers.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Xorshift {
+    state: u64,
+}
+
+impl Xorshift {
+    /// # Panics
+    ///
+    /// If `seed` is 0, which the generator would never leave.
+    pub fn new(seed: u64) -> Xorshift {
+        assert_ne!(seed, 0, "a xorshift seed cannot be 0");
+        Xorshift { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    /// A number below `bound`, which must not be 0.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_xorshift() {
+        let mut random = Xorshift::new(1);
+        assert_eq!(random.next_u64(), 1082269761);
+        let mut again = Xorshift::new(0x2015);
+        let numbers: Vec<u64> = (0..100).map(|_| again.below(6)).collect();
+        assert!(numbers.iter().all(|&n| n < 6));
+        assert!((0..6).all(|n| numbers.contains(&n)));
+        let mut again = Xorshift::new(0x2015);
+        assert!(numbers.iter().all(|&n| again.below(6) == n));
+    }
+}
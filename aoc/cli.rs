@@ -0,0 +1,240 @@
+use std::collections::BTreeMap;
+
+pub const USAGE: &str = "usage:
+    aoc run <year> [<day> [<part>]] [--input <path|->]
+    aoc run --all
+    aoc verify <year> [<day> [<part>]]
+    aoc verify --all
+    aoc bench <year> [<day> [<part>]] [--runs <n>] [--save <file>]
+                                      [--baseline <file>] [--tolerance <percent>]
+    aoc bench --all [...]
+    aoc new <year> <day>
+    aoc fetch <year> [<day>]
+    aoc fetch --all";
+
+#[derive(PartialEq, Debug)]
+pub enum Selection {
+    All,
+    Year(u16),
+    Day(u16, u8),
+    Part(u16, u8, u8),
+}
+
+#[derive(PartialEq, Debug)]
+pub struct BenchOptions {
+    pub runs: usize,
+    /// where to write the measurements as a JSON baseline
+    pub save: Option<String>,
+    /// a previously saved baseline to compare against
+    pub baseline: Option<String>,
+    /// how much slower than the baseline a median may get, in percent
+    pub tolerance: f64,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        BenchOptions {
+            runs: 10,
+            save: None,
+            baseline: None,
+            tolerance: 10.0,
+        }
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub enum Command {
+    Run {
+        selection: Selection,
+        /// overrides the default input location, `-` reads stdin
+        input: Option<String>,
+    },
+    /// checks answers against the recorded `answers.toml`
+    Verify { selection: Selection },
+    Bench {
+        selection: Selection,
+        options: BenchOptions,
+    },
+    /// scaffolds the crate of a new day
+    New { year: u16, day: u8 },
+    /// downloads the inputs that are not cached yet
+    Fetch { selection: Selection },
+}
+
+pub fn parse_args(args: &[String]) -> Result<Command, String> {
+    let mut args = args.iter().map(String::as_str);
+    match args.next() {
+        Some("run") => {
+            let (positional, mut flags) = parse_flags(args, &["--input"])?;
+            let selection = parse_selection(&positional)?;
+            let input = flags.remove("--input").map(String::from);
+            if input.is_some() && !matches!(selection, Selection::Day(..) | Selection::Part(..)) {
+                return Err("--input requires a single day".to_string());
+            }
+            Ok(Command::Run { selection, input })
+        }
+        Some("verify") => {
+            let (positional, _) = parse_flags(args, &[])?;
+            let selection = parse_selection(&positional)?;
+            Ok(Command::Verify { selection })
+        }
+        Some("bench") => {
+            let (positional, mut flags) =
+                parse_flags(args, &["--runs", "--save", "--baseline", "--tolerance"])?;
+            let selection = parse_selection(&positional)?;
+            let mut options = BenchOptions::default();
+            if let Some(runs) = flags.remove("--runs") {
+                options.runs = match runs.parse::<usize>() {
+                    Ok(runs) if runs > 0 => runs,
+                    _ => return Err(format!("invalid number of runs: {runs}")),
+                };
+            }
+            if let Some(tolerance) = flags.remove("--tolerance") {
+                options.tolerance = tolerance
+                    .parse::<f64>()
+                    .map_err(|e| format!("invalid tolerance {tolerance}: {e}"))?;
+            }
+            options.save = flags.remove("--save").map(String::from);
+            options.baseline = flags.remove("--baseline").map(String::from);
+            Ok(Command::Bench { selection, options })
+        }
+        Some("new") => {
+            let (positional, _) = parse_flags(args, &[])?;
+            match parse_selection(&positional)? {
+                Selection::Day(year, day) => Ok(Command::New { year, day }),
+                _ => Err("expected a year and a day".to_string()),
+            }
+        }
+        Some("fetch") => {
+            let (positional, _) = parse_flags(args, &[])?;
+            match parse_selection(&positional)? {
+                Selection::Part(..) => Err("inputs are fetched per day".to_string()),
+                selection => Ok(Command::Fetch { selection }),
+            }
+        }
+        Some(unknown) => Err(format!("unknown command: {unknown}")),
+        None => Err("expected a command".to_string()),
+    }
+}
+
+/// Splits `args` into positional arguments and the values given to `flags`.
+fn parse_flags<'a>(
+    mut args: impl Iterator<Item = &'a str>,
+    flags: &[&'a str],
+) -> Result<(Vec<&'a str>, BTreeMap<&'a str, &'a str>), String> {
+    let mut positional = Vec::new();
+    let mut values = BTreeMap::new();
+    while let Some(arg) = args.next() {
+        if let Some(flag) = flags.iter().find(|flag| **flag == arg) {
+            let value = args
+                .next()
+                .ok_or_else(|| format!("expected a value after {flag}"))?;
+            values.insert(*flag, value);
+        } else if arg.starts_with("--") && arg != "--all" {
+            return Err(format!("unknown flag: {arg}"));
+        } else {
+            positional.push(arg);
+        }
+    }
+    Ok((positional, values))
+}
+
+fn parse_selection(args: &[&str]) -> Result<Selection, String> {
+    let year = |year: &str| {
+        year.parse::<u16>()
+            .map_err(|e| format!("invalid year {year}: {e}"))
+    };
+    let day = |day: &str| {
+        day.parse::<u8>()
+            .map_err(|e| format!("invalid day {day}: {e}"))
+    };
+    match *args {
+        ["--all"] => Ok(Selection::All),
+        [y] => Ok(Selection::Year(year(y)?)),
+        [y, d] => Ok(Selection::Day(year(y)?, day(d)?)),
+        [y, d, part] => match part.parse::<u8>() {
+            Ok(part @ (1 | 2)) => Ok(Selection::Part(year(y)?, day(d)?, part)),
+            _ => Err(format!("invalid part {part}: expected 1 or 2")),
+        },
+        [] => Err("expected a year or --all".to_string()),
+        [_, _, _, extra, ..] => Err(format!("unexpected argument: {extra}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let run = |selection, input: Option<&str>| {
+            Ok(Command::Run {
+                selection,
+                input: input.map(String::from),
+            })
+        };
+        assert_eq!(parse_args(&args("run --all")), run(Selection::All, None));
+        assert_eq!(
+            parse_args(&args("run 2015")),
+            run(Selection::Year(2015), None)
+        );
+        assert_eq!(
+            parse_args(&args("run 2015 7")),
+            run(Selection::Day(2015, 7), None)
+        );
+        assert_eq!(
+            parse_args(&args("run 2015 7 2")),
+            run(Selection::Part(2015, 7, 2), None)
+        );
+        assert_eq!(
+            parse_args(&args("run 2015 7 --input -")),
+            run(Selection::Day(2015, 7), Some("-"))
+        );
+        assert!(parse_args(&args("run 2015 --input -")).is_err());
+        assert!(parse_args(&args("run 2015 7 --input")).is_err());
+        assert!(parse_args(&args("run 2015 7 3")).is_err());
+        assert!(parse_args(&args("run 2015 7 2 1")).is_err());
+        assert_eq!(
+            parse_args(&args("verify --all")),
+            Ok(Command::Verify {
+                selection: Selection::All
+            })
+        );
+        assert!(parse_args(&args("verify 2015 --input -")).is_err());
+        assert_eq!(
+            parse_args(&args("new 2015 8")),
+            Ok(Command::New { year: 2015, day: 8 })
+        );
+        assert!(parse_args(&args("new 2015")).is_err());
+        assert_eq!(
+            parse_args(&args("fetch 2015")),
+            Ok(Command::Fetch {
+                selection: Selection::Year(2015)
+            })
+        );
+        assert!(parse_args(&args("fetch 2015 1 2")).is_err());
+        assert!(parse_args(&args("walk 2015")).is_err());
+        assert!(parse_args(&args("run")).is_err());
+    }
+
+    #[test]
+    fn test_parse_bench_args() {
+        assert_eq!(
+            parse_args(&args("bench 2015 6 --runs 3 --baseline base.json")),
+            Ok(Command::Bench {
+                selection: Selection::Day(2015, 6),
+                options: BenchOptions {
+                    runs: 3,
+                    baseline: Some("base.json".to_string()),
+                    ..BenchOptions::default()
+                }
+            })
+        );
+        assert!(parse_args(&args("bench 2015 --runs 0")).is_err());
+        assert!(parse_args(&args("bench 2015 --tolerance x")).is_err());
+    }
+}
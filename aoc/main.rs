@@ -0,0 +1,344 @@
+mod cli;
+mod registry;
+mod scaffold;
+
+use aoc_core::{
+    Answer,
+    answers::{Answers, Verdict},
+    bench::{self, Measurement},
+    fetch::{self, Fetcher},
+};
+use cli::{BenchOptions, Command, Selection, USAGE, parse_args};
+use registry::{PUZZLES, Puzzle};
+use std::{
+    collections::{BTreeMap, btree_map::Entry},
+    path::Path,
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+fn select(selection: &Selection) -> Vec<(&'static Puzzle, &'static [u8])> {
+    PUZZLES
+        .iter()
+        .filter(|puzzle| match *selection {
+            Selection::All => true,
+            Selection::Year(year) => puzzle.year == year,
+            Selection::Day(year, day) | Selection::Part(year, day, _) => {
+                puzzle.year == year && puzzle.day == day
+            }
+        })
+        .map(|puzzle| {
+            let parts: &[u8] = match *selection {
+                Selection::Part(_, _, 1) => &[1],
+                Selection::Part(_, _, 2) => &[2],
+                _ => &[1, 2],
+            };
+            (puzzle, parts)
+        })
+        .collect()
+}
+
+struct Outcome {
+    year: u16,
+    day: u8,
+    part: u8,
+    answer: Result<Answer, String>,
+    elapsed: Duration,
+}
+
+fn solve(puzzle: &Puzzle, part: u8, input: &Result<String, String>) -> Outcome {
+    let start = Instant::now();
+    let answer = input.as_ref().map_err(Clone::clone).and_then(|input| {
+        let solve = puzzle.part(part).expect("part should be 1 or 2");
+        solve(input).map_err(|e| format!("{e:#}"))
+    });
+    Outcome {
+        year: puzzle.year,
+        day: puzzle.day,
+        part,
+        answer,
+        elapsed: start.elapsed(),
+    }
+}
+
+fn solve_selected(selection: &Selection, input: Option<&str>) -> Result<Vec<Outcome>, String> {
+    let selected = select(selection);
+    if selected.is_empty() {
+        return Err("no puzzle matches the selection".to_string());
+    }
+    let mut outcomes: Vec<Outcome> = Vec::new();
+    for (puzzle, parts) in selected {
+        let puzzle_input =
+            aoc_core::input::read(puzzle.year, puzzle.day, input).map_err(|e| e.to_string());
+        for part in parts {
+            outcomes.push(solve(puzzle, *part, &puzzle_input));
+        }
+    }
+    Ok(outcomes)
+}
+
+fn answer_cell(answer: &Result<Answer, String>) -> String {
+    match answer {
+        Ok(answer) => answer.to_string(),
+        Err(e) => format!("error: {}", e.lines().next().unwrap_or_default()),
+    }
+}
+
+/// Prints the full error of every failed outcome, once per puzzle when both parts fail alike.
+fn print_errors(outcomes: &[Outcome]) {
+    let mut previous: Option<(u16, u8, &str)> = None;
+    for outcome in outcomes {
+        let Err(e) = &outcome.answer else { continue };
+        let current = Some((outcome.year, outcome.day, e.as_str()));
+        if current != previous {
+            eprintln!(
+                "\n{} day {} part {}: {e}",
+                outcome.year, outcome.day, outcome.part
+            );
+        }
+        previous = current;
+    }
+}
+
+fn print_table(header: &[&str], rows: &[Vec<String>]) {
+    let mut widths: Vec<usize> = header.iter().map(|h| h.len()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+    let format_row = |cells: Vec<&str>| {
+        let line: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect();
+        line.join("  ").trim_end().to_string()
+    };
+    println!("{}", format_row(header.to_vec()));
+    for row in rows {
+        println!("{}", format_row(row.iter().map(String::as_str).collect()));
+    }
+}
+
+fn print_summary(outcomes: &[Outcome]) {
+    let rows: Vec<Vec<String>> = outcomes
+        .iter()
+        .map(|o| {
+            vec![
+                o.year.to_string(),
+                o.day.to_string(),
+                o.part.to_string(),
+                answer_cell(&o.answer),
+                format!("{:?}", o.elapsed),
+            ]
+        })
+        .collect();
+    print_table(&["year", "day", "part", "answer", "time"], &rows);
+    let total: Duration = outcomes.iter().map(|o| o.elapsed).sum();
+    println!("total time: {total:?}");
+    print_errors(outcomes);
+}
+
+fn run(selection: &Selection, input: Option<&str>) -> Result<bool, String> {
+    let outcomes = solve_selected(selection, input)?;
+    if let [outcome] = outcomes.as_slice() {
+        match &outcome.answer {
+            Ok(answer) => println!("{answer}"),
+            Err(e) => eprintln!("{e}"),
+        }
+    } else {
+        print_summary(&outcomes);
+    }
+    Ok(outcomes.iter().all(|o| o.answer.is_ok()))
+}
+
+fn verify(selection: &Selection) -> Result<bool, String> {
+    let outcomes = solve_selected(selection, None)?;
+    let mut answers: BTreeMap<u16, Answers> = BTreeMap::new();
+    let mut rows = Vec::new();
+    let mut all_passed = true;
+    for outcome in &outcomes {
+        let recorded = match answers.entry(outcome.year) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                entry.insert(Answers::load(outcome.year).map_err(|e| e.to_string())?)
+            }
+        };
+        let verdict = match &outcome.answer {
+            Ok(answer) => recorded.check(outcome.day, outcome.part, answer),
+            Err(_) => Verdict::Missing,
+        };
+        let (status, expected) = match (&outcome.answer, verdict) {
+            (Err(_), _) => ("ERROR", String::new()),
+            (Ok(_), Verdict::Pass) => ("PASS", String::new()),
+            (Ok(_), Verdict::Fail { expected }) => ("FAIL", expected),
+            (Ok(_), Verdict::Missing) => ("MISSING", String::new()),
+        };
+        all_passed &= matches!(status, "PASS" | "MISSING");
+        rows.push(vec![
+            outcome.year.to_string(),
+            outcome.day.to_string(),
+            outcome.part.to_string(),
+            status.to_string(),
+            answer_cell(&outcome.answer),
+            expected,
+            format!("{:?}", outcome.elapsed),
+        ]);
+    }
+    print_table(
+        &[
+            "year", "day", "part", "status", "answer", "expected", "time",
+        ],
+        &rows,
+    );
+    print_errors(&outcomes);
+    Ok(all_passed)
+}
+
+fn format_ns(ns: u64) -> String {
+    format!("{:?}", Duration::from_nanos(ns))
+}
+
+fn benchmark(selection: &Selection, options: &BenchOptions) -> Result<bool, String> {
+    let selected = select(selection);
+    if selected.is_empty() {
+        return Err("no puzzle matches the selection".to_string());
+    }
+    let mut measurements: Vec<Measurement> = Vec::new();
+    for (puzzle, parts) in selected {
+        let input =
+            aoc_core::input::read(puzzle.year, puzzle.day, None).map_err(|e| e.to_string())?;
+        let puzzle_measurements = (puzzle.bench)(&input, options.runs, parts)
+            .map_err(|e| format!("{} day {}: {e}", puzzle.year, puzzle.day))?;
+        measurements.extend(puzzle_measurements);
+    }
+
+    let baseline = match &options.baseline {
+        Some(path) => bench::load(Path::new(path)).map_err(|e| e.to_string())?,
+        None => Vec::new(),
+    };
+    let tolerance = options.tolerance / 100.0;
+    let comparisons = bench::compare(&measurements, &baseline);
+    let rows: Vec<Vec<String>> = comparisons
+        .iter()
+        .map(|c| {
+            let m = c.current;
+            let mut row = vec![
+                m.year.to_string(),
+                m.day.to_string(),
+                m.phase.name().to_string(),
+                format_ns(m.min_ns),
+                format_ns(m.median_ns),
+                format_ns(m.p95_ns),
+            ];
+            if options.baseline.is_some() {
+                row.extend(match (c.baseline, c.change()) {
+                    (Some(b), Some(change)) => [
+                        format_ns(b.median_ns),
+                        format!("{:+.1}%", change * 100.0),
+                        if c.is_regression(tolerance) {
+                            "REGRESSED"
+                        } else {
+                            "ok"
+                        }
+                        .to_string(),
+                    ],
+                    _ => [String::new(), String::new(), "new".to_string()],
+                });
+            }
+            row
+        })
+        .collect();
+    let mut header = vec!["year", "day", "phase", "min", "median", "p95"];
+    if options.baseline.is_some() {
+        header.extend(["baseline", "change", "status"]);
+    }
+    println!("{} runs per phase", options.runs);
+    print_table(&header, &rows);
+
+    if let Some(path) = &options.save {
+        bench::save(Path::new(path), &measurements).map_err(|e| e.to_string())?;
+        println!("baseline saved to {path}");
+    }
+    Ok(!comparisons.iter().any(|c| c.is_regression(tolerance)))
+}
+
+fn new_day(year: u16, day: u8) -> Result<bool, String> {
+    let written = scaffold::new_day(aoc_core::input::workspace_root(), year, day)?;
+    for path in written {
+        println!("wrote {}", path.display());
+    }
+    println!(
+        "add your input as {}, or download it with `aoc fetch {year} {day}`",
+        aoc_core::input::locate(year, day).display()
+    );
+    Ok(true)
+}
+
+fn fetch(selection: &Selection) -> Result<bool, String> {
+    let selected = select(selection);
+    if selected.is_empty() {
+        return Err("no puzzle matches the selection".to_string());
+    }
+    let session = fetch::session().map_err(|e| e.to_string())?;
+    let fetcher = Fetcher::new(session, aoc_core::input::input_dir());
+    let mut all_fetched = true;
+    for (puzzle, _) in selected {
+        match fetcher.fetch(puzzle.year, puzzle.day) {
+            Ok((path, true)) => println!("downloaded {}", path.display()),
+            Ok((path, false)) => println!("cached {}", path.display()),
+            Err(e) => {
+                eprintln!("{} day {}: {e}", puzzle.year, puzzle.day);
+                all_fetched = false;
+            }
+        }
+    }
+    Ok(all_fetched)
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = match parse_args(&args) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{e}\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let result = match command {
+        Command::Run { selection, input } => run(&selection, input.as_deref()),
+        Command::Verify { selection } => verify(&selection),
+        Command::Bench { selection, options } => benchmark(&selection, &options),
+        Command::New { year, day } => new_day(year, day),
+        Command::Fetch { selection } => fetch(&selection),
+    };
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select() {
+        let parts = |selection| -> usize {
+            select(&selection)
+                .iter()
+                .map(|(_, parts)| parts.len())
+                .sum()
+        };
+        assert_eq!(parts(Selection::Part(2015, 7, 2)), 1);
+        assert_eq!(parts(Selection::Day(2015, 1)), 2);
+        assert_eq!(parts(Selection::Year(2015)), 2 * 7);
+        assert_eq!(parts(Selection::Year(2014)), 0);
+    }
+}
@@ -0,0 +1,41 @@
+use aoc_core::{Answer, Error, Solver, bench::Measurement, solve_part1, solve_part2};
+
+pub type PartSolver = fn(&str) -> Result<Answer, Error>;
+
+/// Times parsing and the given parts over a number of runs.
+pub type Bench = fn(&str, usize, &[u8]) -> Result<Vec<Measurement>, Error>;
+
+pub struct Puzzle {
+    pub year: u16,
+    pub day: u8,
+    pub parts: [PartSolver; 2],
+    pub bench: Bench,
+}
+
+impl Puzzle {
+    pub const fn new<S: Solver>() -> Puzzle {
+        Puzzle {
+            year: S::YEAR,
+            day: S::DAY,
+            parts: [solve_part1::<S>, solve_part2::<S>],
+            bench: aoc_core::bench::bench::<S>,
+        }
+    }
+
+    pub fn part(&self, part: u8) -> Option<PartSolver> {
+        match part {
+            1 | 2 => Some(self.parts[part as usize - 1]),
+            _ => None,
+        }
+    }
+}
+
+pub const PUZZLES: &[Puzzle] = &[
+    Puzzle::new::<_2015_day01::Day01>(),
+    Puzzle::new::<_2015_day02::Day02>(),
+    Puzzle::new::<_2015_day03::Day03>(),
+    Puzzle::new::<_2015_day04::Day04>(),
+    Puzzle::new::<_2015_day05::Day05>(),
+    Puzzle::new::<_2015_day06::Day06>(),
+    Puzzle::new::<_2015_day07::Day07>(),
+];
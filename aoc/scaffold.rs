@@ -0,0 +1,245 @@
+use std::path::{Path, PathBuf};
+
+const CARGO_TOML: &str = r#"[package]
+name = "_%YEAR%_day%DD%"
+version = "0.1.0"
+edition = "2024"
+
+[lib]
+path = "lib.rs"
+
+[[bin]]
+name = "_%YEAR%_day%DD%_part1"
+path = "part1.rs"
+
+[[bin]]
+name = "_%YEAR%_day%DD%_part2"
+path = "part2.rs"
+
+[dependencies]
+aoc_core = { path = "../../aoc_core" }
+"#;
+
+const LIB_RS: &str = r#"use aoc_core::{Answer, Error, Solver};
+
+pub struct Day%DD%;
+
+impl Solver for Day%DD% {
+    const YEAR: u16 = %YEAR%;
+    const DAY: u8 = %DAY%;
+
+    type Input<'a> = Vec<&'a str>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        Ok(input.lines().collect())
+    }
+
+    fn part1(_lines: &Self::Input<'_>) -> Answer {
+        todo!("solve part 1")
+    }
+
+    fn part2(_lines: &Self::Input<'_>) -> Answer {
+        todo!("solve part 2")
+    }
+}
+
+aoc_core::examples!(Day%DD%: example);
+"#;
+
+/// Expected answers of the example, filled in from the puzzle description.
+const EXAMPLE_TOML: &str = "# part1 = 0\n# part2 = 0\n";
+
+const PART_RS: &str = r#"use _%YEAR%_day%DD%::Day%DD%;
+use aoc_core::{Solver, input};
+
+fn main() -> Result<(), aoc_core::Error> {
+    let input_file_content =
+        input::read(Day%DD%::YEAR, Day%DD%::DAY, std::env::args().nth(1).as_deref())?;
+    let lines = Day%DD%::parse(&input_file_content)?;
+    let result = Day%DD%::part%PART%(&lines);
+    println!("{result}");
+    Ok(())
+}
+"#;
+
+fn render(template: &str, year: u16, day: u8) -> String {
+    template
+        .replace("%YEAR%", &year.to_string())
+        .replace("%DD%", &format!("{day:02}"))
+        .replace("%DAY%", &day.to_string())
+}
+
+/// Inserts `line` into the block of lines following `header`, after the last line
+/// sorting before it, so that sorted blocks stay sorted.
+///
+/// The block ends at the first blank line, closing bracket or table header.
+fn insert_sorted(text: &str, header: &str, line: &str) -> Result<String, String> {
+    let lines: Vec<&str> = text.lines().collect();
+    let start = lines
+        .iter()
+        .position(|l| l.trim() == header)
+        .ok_or_else(|| format!("expected a line {header:?}"))?
+        + 1;
+    let end = lines[start..]
+        .iter()
+        .position(|l| {
+            let l = l.trim();
+            l.is_empty() || l == "]" || l == "];" || l.starts_with('[')
+        })
+        .map_or(lines.len(), |i| start + i);
+    let block = &lines[start..end];
+    if block.iter().any(|l| l.trim() == line.trim()) {
+        return Err(format!("{} is already registered", line.trim()));
+    }
+    let at = block
+        .iter()
+        .rposition(|l| l.trim() < line.trim())
+        .map_or(start, |i| start + i + 1);
+
+    let mut updated: Vec<&str> = lines[..at].to_vec();
+    updated.push(line);
+    updated.extend(&lines[at..]);
+    let mut updated = updated.join("\n");
+    if text.ends_with('\n') {
+        updated.push('\n');
+    }
+    Ok(updated)
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|e| format!("failed to read {}: {e}", path.display()))
+}
+
+fn write(path: &Path, content: &str) -> Result<(), String> {
+    std::fs::write(path, content).map_err(|e| format!("failed to write {}: {e}", path.display()))
+}
+
+/// Creates the crate of a new day under `root` from a template implementing `Solver`,
+/// and registers it in the workspace and the runner. Returns the files written.
+pub fn new_day(root: &Path, year: u16, day: u8) -> Result<Vec<PathBuf>, String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("invalid day {day}: expected 1 to 25"));
+    }
+    let dir = root.join(format!("_{year}/day{day:02}"));
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()));
+    }
+    let krate = format!("_{year}_day{day:02}");
+
+    // update the existing files in memory first, so nothing is written if one of them fails
+    let workspace = root.join("Cargo.toml");
+    let runner = root.join("aoc/Cargo.toml");
+    let registry = root.join("aoc/registry.rs");
+    let updates = [
+        (
+            insert_sorted(
+                &read(&workspace)?,
+                "members = [",
+                &format!("    \"_{year}/day{day:02}\","),
+            )?,
+            workspace,
+        ),
+        (
+            insert_sorted(
+                &read(&runner)?,
+                "[dependencies]",
+                &format!("{krate} = {{ path = \"../_{year}/day{day:02}\" }}"),
+            )?,
+            runner,
+        ),
+        (
+            insert_sorted(
+                &read(&registry)?,
+                "pub const PUZZLES: &[Puzzle] = &[",
+                &format!("    Puzzle::new::<{krate}::Day{day:02}>(),"),
+            )?,
+            registry,
+        ),
+    ];
+
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("failed to create {}: {e}", dir.display()))?;
+    std::fs::create_dir_all(dir.join("examples"))
+        .map_err(|e| format!("failed to create {}: {e}", dir.display()))?;
+    let files = [
+        ("Cargo.toml", render(CARGO_TOML, year, day)),
+        ("lib.rs", render(LIB_RS, year, day)),
+        (
+            "part1.rs",
+            render(PART_RS, year, day).replace("%PART%", "1"),
+        ),
+        (
+            "part2.rs",
+            render(PART_RS, year, day).replace("%PART%", "2"),
+        ),
+        ("examples/example.txt", String::new()),
+        ("examples/example.toml", EXAMPLE_TOML.to_string()),
+    ];
+    let mut written = Vec::new();
+    for (name, content) in files {
+        let path = dir.join(name);
+        write(&path, &content)?;
+        written.push(path);
+    }
+    for (content, path) in updates {
+        write(&path, &content)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_sorted() {
+        let manifest = "[workspace]\nmembers = [\n    \"aoc\",\n    \"_2015\",\n    \"_2015/day01\",\n    \"_2015/day03\",\n]\n";
+        let updated = insert_sorted(manifest, "members = [", "    \"_2015/day02\",").unwrap();
+        assert_eq!(
+            updated,
+            manifest.replace("day01\",\n", "day01\",\n    \"_2015/day02\",\n")
+        );
+        let updated = insert_sorted(manifest, "members = [", "    \"_2016/day01\",").unwrap();
+        assert!(updated.ends_with("    \"_2015/day03\",\n    \"_2016/day01\",\n]\n"));
+        assert!(insert_sorted(manifest, "members = [", "    \"_2015/day01\",").is_err());
+        assert!(insert_sorted(manifest, "[dependencies]", "x").is_err());
+
+        let dependencies = "[dependencies]\n_2015_day01 = { path = \"../_2015/day01\" }\naoc_core = { path = \"../aoc_core\" }";
+        let updated = insert_sorted(
+            dependencies,
+            "[dependencies]",
+            "_2015_day02 = { path = \"../_2015/day02\" }",
+        )
+        .unwrap();
+        assert_eq!(
+            updated.lines().nth(2),
+            Some("_2015_day02 = { path = \"../_2015/day02\" }")
+        );
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = std::env::temp_dir().join(format!("aoc-new-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("aoc")).unwrap();
+        std::fs::write(root.join("Cargo.toml"), "members = [\n    \"aoc\",\n]\n").unwrap();
+        std::fs::write(root.join("aoc/Cargo.toml"), "[dependencies]\n").unwrap();
+        std::fs::write(
+            root.join("aoc/registry.rs"),
+            "pub const PUZZLES: &[Puzzle] = &[\n];\n",
+        )
+        .unwrap();
+
+        let written = new_day(&root, 2016, 8).unwrap();
+        assert_eq!(written.len(), 9);
+        let lib = std::fs::read_to_string(root.join("_2016/day08/lib.rs")).unwrap();
+        assert!(lib.contains("pub struct Day08;"));
+        assert!(lib.contains("const DAY: u8 = 8;"));
+        let registry = std::fs::read_to_string(root.join("aoc/registry.rs")).unwrap();
+        assert!(registry.contains("Puzzle::new::<_2016_day08::Day08>(),"));
+        assert!(new_day(&root, 2016, 8).is_err());
+        assert!(new_day(&root, 2016, 26).is_err());
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,116 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use crate::{Answer, Error, input};
+
+/// The outcome of checking a computed answer against the recorded one.
+#[derive(PartialEq, Debug)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Missing,
+}
+
+/// Known good answers of one year, read from `_<year>/answers.toml`:
+///
+/// ```toml
+/// [day01]
+/// part1 = 138
+/// part2 = 1771
+/// ```
+#[derive(PartialEq, Debug, Default)]
+pub struct Answers(BTreeMap<(u8, u8), String>);
+
+impl Answers {
+    pub fn path(year: u16) -> PathBuf {
+        input::input_dir().join(format!("_{year}/answers.toml"))
+    }
+
+    /// Loads the answers of `year`, a missing file means no answer is recorded yet.
+    pub fn load(year: u16) -> Result<Answers, Error> {
+        let path = Answers::path(year);
+        match std::fs::read_to_string(&path) {
+            Ok(content) => {
+                Answers::parse(&content).map_err(|e| format!("{}: {e}", path.display()).into())
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(format!("failed to read {}: {e}", path.display()).into()),
+        }
+    }
+
+    pub fn parse(content: &str) -> Result<Answers, Error> {
+        let table: toml::Table = content.parse()?;
+        let mut answers = BTreeMap::new();
+        for (day_key, parts) in table {
+            let day = day_key
+                .strip_prefix("day")
+                .and_then(|day| day.parse::<u8>().ok())
+                .ok_or_else(|| format!("expected a [dayNN] table, found [{day_key}]"))?;
+            let parts = parts
+                .as_table()
+                .ok_or_else(|| format!("expected [{day_key}] to be a table"))?;
+            for (part, answer) in parse_parts(&day_key, parts)? {
+                answers.insert((day, part), answer);
+            }
+        }
+        Ok(Answers(answers))
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.0.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn check(&self, day: u8, part: u8, answer: &Answer) -> Verdict {
+        match self.get(day, part) {
+            Some(expected) if expected == answer.to_string() => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+            },
+            None => Verdict::Missing,
+        }
+    }
+}
+
+/// Reads the `part1` and `part2` answers of `table`, named `name` in error messages.
+pub(crate) fn parse_parts(name: &str, table: &toml::Table) -> Result<Vec<(u8, String)>, Error> {
+    let mut answers = Vec::new();
+    for (part_key, value) in table {
+        let part = match part_key.as_str() {
+            "part1" => 1,
+            "part2" => 2,
+            _ => return Err(format!("unknown key {name}.{part_key}").into()),
+        };
+        let answer = match value {
+            toml::Value::String(s) => s.clone(),
+            toml::Value::Integer(i) => i.to_string(),
+            _ => return Err(format!("{name}.{part_key} is not a string or integer").into()),
+        };
+        answers.push((part, answer));
+    }
+    Ok(answers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let answers = Answers::parse("[day01]\npart1 = 138\npart2 = \"abc\"\n").unwrap();
+        assert_eq!(answers.check(1, 1, &Answer::from(138u32)), Verdict::Pass);
+        assert_eq!(
+            answers.check(1, 1, &Answer::from(-138)),
+            Verdict::Fail {
+                expected: "138".to_string()
+            }
+        );
+        assert_eq!(answers.check(1, 2, &Answer::from("abc")), Verdict::Pass);
+        assert_eq!(answers.check(2, 1, &Answer::from(1u8)), Verdict::Missing);
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_keys() {
+        assert!(Answers::parse("[one]\npart1 = 1").is_err());
+        assert!(Answers::parse("[day01]\npart3 = 1").is_err());
+        assert!(Answers::parse("[day01]\npart1 = 1.5").is_err());
+    }
+}
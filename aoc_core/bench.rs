@@ -0,0 +1,193 @@
+use std::{
+    hint::black_box,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, Solver};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Phase {
+    pub fn name(self) -> &'static str {
+        match self {
+            Phase::Parse => "parse",
+            Phase::Part1 => "part1",
+            Phase::Part2 => "part2",
+        }
+    }
+}
+
+/// Timings of one phase of a puzzle over repeated runs, in nanoseconds.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Measurement {
+    pub year: u16,
+    pub day: u8,
+    pub phase: Phase,
+    pub runs: usize,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub p95_ns: u64,
+}
+
+impl Measurement {
+    fn from_samples(year: u16, day: u8, phase: Phase, mut samples: Vec<Duration>) -> Measurement {
+        assert!(!samples.is_empty(), "at least one run is needed");
+        samples.sort();
+        let nanos = |d: Duration| d.as_nanos() as u64;
+        // nearest-rank percentile
+        let p95 = (samples.len() * 95).div_ceil(100) - 1;
+        Measurement {
+            year,
+            day,
+            phase,
+            runs: samples.len(),
+            min_ns: nanos(samples[0]),
+            median_ns: nanos(samples[samples.len() / 2]),
+            p95_ns: nanos(samples[p95]),
+        }
+    }
+
+    fn is_same_phase(&self, other: &Measurement) -> bool {
+        self.year == other.year && self.day == other.day && self.phase == other.phase
+    }
+}
+
+fn time_runs<T>(runs: usize, mut f: impl FnMut() -> T) -> Vec<Duration> {
+    (0..runs)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect()
+}
+
+/// Times parsing and the requested `parts` of a puzzle separately, `runs` times each.
+pub fn bench<S: Solver>(input: &str, runs: usize, parts: &[u8]) -> Result<Vec<Measurement>, Error> {
+    let parsed = S::parse(input)?;
+    let mut measurements = vec![Measurement::from_samples(
+        S::YEAR,
+        S::DAY,
+        Phase::Parse,
+        time_runs(runs, || S::parse(black_box(input))),
+    )];
+    for part in parts {
+        let (phase, samples) = match part {
+            1 => (
+                Phase::Part1,
+                time_runs(runs, || S::part1(black_box(&parsed))),
+            ),
+            2 => (
+                Phase::Part2,
+                time_runs(runs, || S::part2(black_box(&parsed))),
+            ),
+            _ => return Err(format!("invalid part {part}: expected 1 or 2").into()),
+        };
+        measurements.push(Measurement::from_samples(S::YEAR, S::DAY, phase, samples));
+    }
+    Ok(measurements)
+}
+
+pub fn save(path: &Path, measurements: &[Measurement]) -> Result<(), Error> {
+    let json = serde_json::to_string_pretty(measurements)?;
+    std::fs::write(path, json).map_err(|e| format!("failed to write {}: {e}", path.display()))?;
+    Ok(())
+}
+
+pub fn load(path: &Path) -> Result<Vec<Measurement>, Error> {
+    let json = std::fs::read_to_string(path)
+        .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+    Ok(serde_json::from_str(&json)?)
+}
+
+/// A measurement next to the same phase of a baseline run.
+#[derive(PartialEq, Debug)]
+pub struct Comparison<'a> {
+    pub current: &'a Measurement,
+    pub baseline: Option<&'a Measurement>,
+}
+
+impl Comparison<'_> {
+    /// Relative change of the median time, `0.1` is 10% slower than the baseline.
+    pub fn change(&self) -> Option<f64> {
+        self.baseline.map(|b| {
+            (self.current.median_ns as f64 - b.median_ns as f64) / b.median_ns.max(1) as f64
+        })
+    }
+
+    pub fn is_regression(&self, tolerance: f64) -> bool {
+        self.change().is_some_and(|change| change > tolerance)
+    }
+}
+
+pub fn compare<'a>(current: &'a [Measurement], baseline: &'a [Measurement]) -> Vec<Comparison<'a>> {
+    current
+        .iter()
+        .map(|m| Comparison {
+            current: m,
+            baseline: baseline.iter().find(|b| b.is_same_phase(m)),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measurement(phase: Phase, median_ns: u64) -> Measurement {
+        Measurement {
+            year: 2015,
+            day: 1,
+            phase,
+            runs: 1,
+            min_ns: median_ns,
+            median_ns,
+            p95_ns: median_ns,
+        }
+    }
+
+    #[test]
+    fn test_from_samples() {
+        let samples = (1..=20).rev().map(Duration::from_nanos).collect();
+        let m = Measurement::from_samples(2015, 1, Phase::Parse, samples);
+        assert_eq!((m.min_ns, m.median_ns, m.p95_ns), (1, 11, 19));
+        let m = Measurement::from_samples(2015, 1, Phase::Parse, vec![Duration::from_nanos(5)]);
+        assert_eq!((m.min_ns, m.median_ns, m.p95_ns), (5, 5, 5));
+    }
+
+    #[test]
+    fn test_compare_flags_regressions() {
+        let baseline = vec![
+            measurement(Phase::Part1, 100),
+            measurement(Phase::Part2, 100),
+        ];
+        let current = vec![
+            measurement(Phase::Parse, 10),
+            measurement(Phase::Part1, 105),
+            measurement(Phase::Part2, 150),
+        ];
+        let comparisons = compare(&current, &baseline);
+        assert_eq!(comparisons[0].baseline, None);
+        assert!(!comparisons[0].is_regression(0.1));
+        assert!(!comparisons[1].is_regression(0.1));
+        assert!(comparisons[2].is_regression(0.1));
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let measurements = vec![measurement(Phase::Part2, 42)];
+        let json = serde_json::to_string(&measurements).unwrap();
+        assert!(json.contains("\"phase\":\"part2\""));
+        let parsed: Vec<Measurement> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, measurements);
+    }
+}
@@ -0,0 +1,95 @@
+use crate::{Error, Solver, answers::parse_parts};
+
+/// The expected answer of `part` in the content of an example's `.toml` file,
+/// `None` if the puzzle description gives none.
+pub fn expected(content: &str, part: u8) -> Result<Option<String>, Error> {
+    let table: toml::Table = content.parse()?;
+    Ok(parse_parts("example", &table)?
+        .into_iter()
+        .find_map(|(p, answer)| (p == part).then_some(answer)))
+}
+
+/// Solves `part` of the example `name` and compares the answer with the expected one.
+/// Parts without an expected answer are not solved.
+///
+/// # Panics
+///
+/// If the example cannot be parsed or the answer differs, so it can back a `#[test]`.
+pub fn check<S: Solver>(name: &str, input: &str, expected_content: &str, part: u8) {
+    let expected = expected(expected_content, part)
+        .unwrap_or_else(|e| panic!("invalid examples/{name}.toml: {e}"));
+    let Some(expected) = expected else {
+        return;
+    };
+    let parsed = S::parse(input).unwrap_or_else(|e| panic!("failed to parse {name}: {e:#}"));
+    let answer = match part {
+        1 => S::part1(&parsed),
+        _ => S::part2(&parsed),
+    };
+    assert_eq!(answer.to_string(), expected, "{name}, part {part}");
+}
+
+/// Generates a test per part for each example of a day, in a `examples` module.
+///
+/// Every `name` refers to the `examples/<name>.txt` input of the crate, next to
+/// `examples/<name>.toml` holding the answers given by the puzzle description:
+///
+/// ```toml
+/// part1 = 3
+/// part2 = 1
+/// ```
+///
+/// ```ignore
+/// aoc_core::examples!(Day01: balanced, basement_first);
+/// ```
+#[macro_export]
+macro_rules! examples {
+    ($solver:ty: $($name:ident),+ $(,)?) => {
+        #[cfg(test)]
+        mod examples {
+            use super::*;
+            $(
+                mod $name {
+                    use super::*;
+
+                    const INPUT: &str = include_str!(concat!(
+                        env!("CARGO_MANIFEST_DIR"),
+                        "/examples/",
+                        stringify!($name),
+                        ".txt"
+                    ));
+                    const EXPECTED: &str = include_str!(concat!(
+                        env!("CARGO_MANIFEST_DIR"),
+                        "/examples/",
+                        stringify!($name),
+                        ".toml"
+                    ));
+
+                    #[test]
+                    fn part1() {
+                        $crate::examples::check::<$solver>(stringify!($name), INPUT, EXPECTED, 1);
+                    }
+
+                    #[test]
+                    fn part2() {
+                        $crate::examples::check::<$solver>(stringify!($name), INPUT, EXPECTED, 2);
+                    }
+                }
+            )+
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expected() {
+        let content = "part1 = 3\npart2 = \"abc\"";
+        assert_eq!(expected(content, 1).unwrap(), Some("3".to_string()));
+        assert_eq!(expected(content, 2).unwrap(), Some("abc".to_string()));
+        assert_eq!(expected("part1 = -1", 2).unwrap(), None);
+        assert!(expected("part3 = 1", 1).is_err());
+    }
+}
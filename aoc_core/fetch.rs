@@ -0,0 +1,248 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::Deserialize;
+
+use crate::{Error, input};
+
+/// Holds the adventofcode.com session cookie.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Optional config file at the workspace root, e.g. `session = "53616c74..."`.
+pub const CONFIG_FILE: &str = "aoc.toml";
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// The site asks automated tools to identify themselves.
+const USER_AGENT: &str = concat!("aoc_core/", env!("CARGO_PKG_VERSION"), " input fetcher");
+
+/// Minimum time between two requests to the server.
+pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Records when the last request was sent, so the rate limit holds across runs.
+const STAMP_FILE: &str = ".last-fetch";
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Config {
+    session: Option<String>,
+}
+
+/// The session token from `$AOC_SESSION`, or from the `aoc.toml` config file otherwise.
+pub fn session() -> Result<String, Error> {
+    if let Ok(session) = std::env::var(SESSION_VAR) {
+        return Ok(session.trim().to_string());
+    }
+    let path = input::workspace_root().join(CONFIG_FILE);
+    let missing = || format!("no session token: set {SESSION_VAR} or `session` in {CONFIG_FILE}");
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(_) => return Err(missing().into()),
+    };
+    let config: Config =
+        toml::from_str(&content).map_err(|e| format!("invalid {}: {e}", path.display()))?;
+    Ok(config.session.ok_or_else(missing)?.trim().to_string())
+}
+
+/// Downloads puzzle inputs into a cache laid out like the input directory,
+/// so each input is only ever downloaded once.
+pub struct Fetcher {
+    base_url: String,
+    session: String,
+    cache_dir: PathBuf,
+    interval: Duration,
+}
+
+impl Fetcher {
+    pub fn new(session: String, cache_dir: PathBuf) -> Fetcher {
+        Fetcher {
+            base_url: BASE_URL.to_string(),
+            session,
+            cache_dir,
+            interval: DEFAULT_INTERVAL,
+        }
+    }
+
+    /// Sends requests to another server, e.g. a local one in tests.
+    pub fn with_base_url(self, base_url: &str) -> Fetcher {
+        Fetcher {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            ..self
+        }
+    }
+
+    pub fn with_interval(self, interval: Duration) -> Fetcher {
+        Fetcher { interval, ..self }
+    }
+
+    pub fn cached_path(&self, year: u16, day: u8) -> PathBuf {
+        input::locate_in(&self.cache_dir, year, day)
+    }
+
+    /// The cached input of a puzzle, downloaded first if it is not cached yet.
+    /// Returns the path of the input and whether it was downloaded.
+    pub fn fetch(&self, year: u16, day: u8) -> Result<(PathBuf, bool), Error> {
+        let path = self.cached_path(year, day);
+        if path.exists() {
+            return Ok((path, false));
+        }
+        let content = self.download(year, day)?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| format!("failed to create {}: {e}", dir.display()))?;
+        }
+        std::fs::write(&path, content)
+            .map_err(|e| format!("failed to write {}: {e}", path.display()))?;
+        Ok((path, true))
+    }
+
+    fn download(&self, year: u16, day: u8) -> Result<String, Error> {
+        self.wait_for_rate_limit()?;
+        let url = format!("{}/{year}/day/{day}/input", self.base_url);
+        let response = ureq::get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .call();
+        self.stamp()?;
+        match response {
+            Ok(response) => Ok(response.into_string()?),
+            Err(ureq::Error::Status(404, _)) => {
+                Err(format!("{year} day {day} is not available yet").into())
+            }
+            Err(ureq::Error::Status(code @ (400 | 500), _)) => Err(format!(
+                "server answered {code} for {url}, the session token may have expired"
+            )
+            .into()),
+            Err(e) => Err(format!("failed to download {url}: {e}").into()),
+        }
+    }
+
+    fn stamp_path(&self) -> PathBuf {
+        self.cache_dir.join(STAMP_FILE)
+    }
+
+    fn stamp(&self) -> Result<(), Error> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+        std::fs::create_dir_all(&self.cache_dir)?;
+        std::fs::write(self.stamp_path(), now.as_millis().to_string())?;
+        Ok(())
+    }
+
+    /// Sleeps until `interval` has passed since the last request, even one made by another run.
+    fn wait_for_rate_limit(&self) -> Result<(), Error> {
+        let Some(last) = read_stamp(&self.stamp_path()) else {
+            return Ok(());
+        };
+        let since = SystemTime::now()
+            .duration_since(UNIX_EPOCH)?
+            .saturating_sub(last);
+        if since < self.interval {
+            std::thread::sleep(self.interval - since);
+        }
+        Ok(())
+    }
+}
+
+fn read_stamp(path: &Path) -> Option<Duration> {
+    let millis = std::fs::read_to_string(path).ok()?.trim().parse().ok()?;
+    Some(Duration::from_millis(millis))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        sync::mpsc::{self, Receiver},
+        time::Instant,
+    };
+
+    /// Serves `responses` in order on a local port, sending back the request lines received.
+    fn serve(responses: Vec<(u16, &'static str)>) -> (String, Receiver<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = Vec::new();
+                let mut reader = BufReader::new(&stream);
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    request.push(line.trim().to_string());
+                }
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+                sender.send(request).unwrap();
+            }
+        });
+        (url, receiver)
+    }
+
+    fn cache_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-fetch-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_fetch_caches_input() {
+        let (url, requests) = serve(vec![(200, "(()\n")]);
+        let dir = cache_dir("cache");
+        let fetcher = Fetcher::new("secret".to_string(), dir.clone())
+            .with_base_url(&url)
+            .with_interval(Duration::ZERO);
+
+        let (path, downloaded) = fetcher.fetch(2015, 1).unwrap();
+        assert!(downloaded);
+        assert_eq!(path, dir.join("_2015/day01/input.txt"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "(()\n");
+        let request = requests.recv().unwrap();
+        assert_eq!(request[0], "GET /2015/day/1/input HTTP/1.1");
+        assert!(request.iter().any(|h| h == "Cookie: session=secret"));
+
+        // served from the cache, the server would not answer a second request
+        assert_eq!(fetcher.fetch(2015, 1).unwrap(), (path, false));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_reports_locked_puzzle() {
+        let (url, _requests) = serve(vec![(404, "not yet")]);
+        let dir = cache_dir("locked");
+        let fetcher = Fetcher::new("secret".to_string(), dir.clone())
+            .with_base_url(&url)
+            .with_interval(Duration::ZERO);
+        let error = fetcher.fetch(2015, 25).unwrap_err();
+        assert!(error.to_string().contains("not available yet"), "{error}");
+        assert!(!fetcher.cached_path(2015, 25).exists());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_respects_rate_limit() {
+        let (url, _requests) = serve(vec![(200, "1"), (200, "2")]);
+        let dir = cache_dir("rate");
+        let interval = Duration::from_millis(300);
+        let fetcher = Fetcher::new("secret".to_string(), dir.clone())
+            .with_base_url(&url)
+            .with_interval(interval);
+        fetcher.fetch(2015, 1).unwrap();
+        let start = Instant::now();
+        fetcher.fetch(2015, 2).unwrap();
+        assert!(start.elapsed() >= interval / 2);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
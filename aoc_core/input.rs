@@ -0,0 +1,81 @@
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use crate::Error;
+
+/// Overrides the directory puzzle inputs are looked up in.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Reads inputs from stdin instead of a file.
+pub const STDIN: &str = "-";
+
+/// The workspace root, resolved at compile time so it does not depend on the current directory.
+pub fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("aoc_core lives inside the workspace")
+}
+
+/// Where inputs are looked up: `$AOC_INPUT_DIR` if set, the workspace root otherwise.
+pub fn input_dir() -> PathBuf {
+    match std::env::var_os(INPUT_DIR_VAR) {
+        Some(dir) => PathBuf::from(dir),
+        None => workspace_root().to_path_buf(),
+    }
+}
+
+/// The path of an input relative to the input directory, e.g. `_2015/day07/input.txt`.
+pub fn relative_path(year: u16, day: u8) -> PathBuf {
+    PathBuf::from(format!("_{year}/day{day:02}/input.txt"))
+}
+
+pub fn locate_in(dir: &Path, year: u16, day: u8) -> PathBuf {
+    dir.join(relative_path(year, day))
+}
+
+pub fn locate(year: u16, day: u8) -> PathBuf {
+    locate_in(&input_dir(), year, day)
+}
+
+/// Reads the input of a puzzle from `path`, from stdin if `path` is `-`,
+/// or from its default location if no path is given.
+pub fn read(year: u16, day: u8, path: Option<&str>) -> Result<String, Error> {
+    match path {
+        Some(STDIN) => {
+            let mut input = String::new();
+            std::io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| format!("failed to read stdin: {e}"))?;
+            Ok(input)
+        }
+        Some(path) => read_file(Path::new(path)),
+        None => read_file(&locate(year, day)),
+    }
+}
+
+fn read_file(path: &Path) -> Result<String, Error> {
+    std::fs::read_to_string(path)
+        .map_err(|e| format!("failed to read {}: {e}", path.display()).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locate_in() {
+        assert_eq!(
+            locate_in(Path::new("/inputs"), 2015, 7),
+            PathBuf::from("/inputs/_2015/day07/input.txt")
+        );
+    }
+
+    #[test]
+    fn test_read_independent_of_current_dir() {
+        let input = read(2015, 1, None).unwrap();
+        assert!(input.starts_with('('));
+        assert!(read(2015, 1, Some("does/not/exist.txt")).is_err());
+    }
+}
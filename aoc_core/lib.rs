@@ -0,0 +1,86 @@
+use std::fmt::Display;
+
+pub mod answers;
+pub mod bench;
+pub mod examples;
+pub mod fetch;
+pub mod input;
+
+pub type Error = Box<dyn std::error::Error + Send + Sync>;
+
+/// The answer to one part of a puzzle, as submitted on the website.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum Answer {
+    Signed(i64),
+    Unsigned(u64),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Signed(value) => write!(f, "{value}"),
+            Answer::Unsigned(value) => write!(f, "{value}"),
+            Answer::Text(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+macro_rules! answer_from {
+    ($variant:ident as $wide:ty: $($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::$variant(value as $wide)
+                }
+            }
+        )*
+    };
+}
+
+answer_from!(Signed as i64: i8, i16, i32, i64, isize);
+answer_from!(Unsigned as u64: u8, u16, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+/// A puzzle solution: the input is parsed once and shared by both parts.
+pub trait Solver {
+    const YEAR: u16;
+    const DAY: u8;
+
+    type Input<'a>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error>;
+    fn part1(input: &Self::Input<'_>) -> Answer;
+    fn part2(input: &Self::Input<'_>) -> Answer;
+}
+
+pub fn solve_part1<S: Solver>(input: &str) -> Result<Answer, Error> {
+    Ok(S::part1(&S::parse(input)?))
+}
+
+pub fn solve_part2<S: Solver>(input: &str) -> Result<Answer, Error> {
+    Ok(S::part2(&S::parse(input)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer_display() {
+        assert_eq!(Answer::from(-1i32).to_string(), "-1");
+        assert_eq!(Answer::from(1771u32).to_string(), "1771");
+        assert_eq!(Answer::from("abc").to_string(), "abc");
+    }
+}